@@ -1,7 +1,7 @@
 use clap::ValueEnum;
 
 use luwen_if::{
-    chip::{ArcMsgOptions, Chip, HlCommsInterface},
+    chip::{ArcMsgOptions, Chip},
     ArcState, ChipImpl, TypedArcMsg,
 };
 
@@ -27,19 +27,9 @@ pub fn hang_arc(method: ArcHangMethod, chip: Chip) -> Result<(), Box<dyn std::er
             })?;
         }
         ArcHangMethod::CoreHault => {
-            // Need to go into arc a3 before haulting the core, otherwise we can interrupt
+            // Moves the arc into a3 before haulting, so that we don't interrupt
             // communication with the voltage regulator.
-            chip.arc_msg(ArcMsgOptions {
-                msg: TypedArcMsg::SetArcState {
-                    state: ArcState::A3,
-                }
-                .into(),
-                ..Default::default()
-            })?;
-
-            let rmw = chip.axi_sread32("ARC_RESET.ARC_MISC_CNTL")?;
-            // the core hault bits are in 7:4 we only care about core 0 here (aka bit 4)
-            chip.axi_swrite32("ARC_RESET.ARC_MISC_CNTL", rmw | (1 << 4))?;
+            chip.arc_core_hault(0)?;
         }
     }
 
@@ -18,9 +18,10 @@ Limitations:
 */
 
 use clap::Parser;
-use luwen_if::chip::Telemetry;
+use luwen_if::chip::{Telemetry, THROTTLER_NAMES};
 use luwen_if::{ChipImpl, DeviceInfo};
 use prometheus::{register_gauge_vec, GaugeVec, Opts};
+use std::collections::HashMap;
 use std::thread;
 use std::time::Duration;
 
@@ -65,6 +66,15 @@ struct Metrics {
     pci_cur_link_gen: GaugeVec,    // Current PCIe link generation
     pci_max_link_width: GaugeVec,  // Maximum PCIe link width
     pci_max_link_gen: GaugeVec,    // Maximum PCIe link generation
+    l2cpuclk: GaugeVec,            // L2CPU clock per core, MHz (Blackhole only)
+    l2cpu_enabled: GaugeVec,       // Number of running L2CPU cores (Blackhole only)
+    telemetry_stale: GaugeVec,     // 1 if the heartbeat did not advance since the last poll
+    throttling: GaugeVec,          // 1 if any throttler is limiting AICLK
+    throttler_active: GaugeVec,    // 1 for each throttler that is limiting AICLK
+    power_limit: GaugeVec,         // TDP limit, Watts
+
+    // Heartbeat seen on the previous poll, keyed by board_id.
+    last_heartbeat: HashMap<String, u32>,
 }
 
 macro_rules! register_gauge_vec_with_board_id {
@@ -150,11 +160,39 @@ impl Metrics {
                 "Max PCIe width"
             ),
             pci_max_link_gen: register_gauge_vec_with_board_id!("max_pci_link_gen", "Max PCIe gen"),
+            l2cpuclk: {
+                let opts = Opts::new("l2cpuclk", "L2CPUCLK (MHz)")
+                    .namespace("tt")
+                    .subsystem("smi");
+                register_gauge_vec!(opts, &["board_id", "core"]).unwrap()
+            },
+            l2cpu_enabled: register_gauge_vec_with_board_id!(
+                "l2cpu_enabled",
+                "Number of running L2CPU cores"
+            ),
+            telemetry_stale: register_gauge_vec_with_board_id!(
+                "telemetry_stale",
+                "1 if the telemetry heartbeat has not advanced since the last poll"
+            ),
+            throttling: register_gauge_vec_with_board_id!(
+                "throttling",
+                "1 if any throttler is limiting AICLK"
+            ),
+            throttler_active: {
+                let opts = Opts::new("throttler_active", "1 if the throttler is limiting AICLK")
+                    .namespace("tt")
+                    .subsystem("smi");
+                register_gauge_vec!(opts, &["board_id", "throttler"]).unwrap()
+            },
+            power_limit: register_gauge_vec_with_board_id!("power_limit", "TDP Limit (W)"),
+            last_heartbeat: HashMap::new(),
         }
     }
 
     /// Call with telemetry for any board.
-    pub fn update(&self, device_info: &Option<DeviceInfo>, telemetry: &Telemetry) {
+    /// The heartbeat is compared against the previous call for the same board to detect a
+    /// chip whose firmware has stopped updating telemetry, boards that report no heartbeat are skipped.
+    pub fn update(&mut self, device_info: &Option<DeviceInfo>, telemetry: &Telemetry) {
         let mut board_id = telemetry.board_serial_number_hex();
 
         match device_info {
@@ -241,6 +279,37 @@ impl Metrics {
         );
         set_with_board_id!(&self.power, &board_id, telemetry.power());
         set_with_board_id!(&self.current, &board_id, telemetry.current());
+
+        // Only blackhole has L2CPU cores, the clocks read as 0 everywhere else.
+        let l2cpu_enabled = telemetry.l2cpu_enabled_count();
+        if l2cpu_enabled > 0 {
+            set_with_board_id!(&self.l2cpu_enabled, &board_id, l2cpu_enabled);
+            for core in 0..4 {
+                if let Some(clk) = telemetry.l2cpu_clk(core) {
+                    self.l2cpuclk
+                        .with_label_values(&[&board_id, &core.to_string()])
+                        .set(clk as f64);
+                }
+            }
+        }
+
+        set_with_board_id!(&self.throttling, &board_id, telemetry.is_throttling() as u8);
+        set_with_board_id!(&self.power_limit, &board_id, telemetry.power_limit_watts());
+        // Every throttler is set on each poll so that one which stops throttling goes back to 0.
+        let active_throttlers = telemetry.active_throttlers();
+        for name in THROTTLER_NAMES {
+            self.throttler_active
+                .with_label_values(&[&board_id, name])
+                .set(active_throttlers.contains(&name) as u8 as f64);
+        }
+
+        // Firmware that does not report a heartbeat leaves it at 0, staleness can't be detected on those boards.
+        // The first poll of a board has nothing to compare against, so it is never reported as stale.
+        let heartbeat = telemetry.telemetry_heartbeat();
+        if heartbeat != 0 {
+            let stale = self.last_heartbeat.insert(board_id.clone(), heartbeat) == Some(heartbeat);
+            set_with_board_id!(&self.telemetry_stale, &board_id, stale as u8);
+        }
     }
 }
 
@@ -250,7 +319,7 @@ fn main() {
     let chips = luwen_ref::detect_chips().unwrap();
 
     let worker = thread::spawn(move || {
-        let metrics = Metrics::new();
+        let mut metrics = Metrics::new();
 
         loop {
             for chip in &chips {
@@ -13,6 +13,16 @@ pub enum Arch {
 }
 
 impl Arch {
+    /// Map a pci device id to its arch, the id is returned as the error if it is not recognized.
+    pub fn from_device_id(device_id: u16) -> Result<Arch, u16> {
+        match device_id {
+            0xfaca => Ok(Arch::Grayskull),
+            0x401e => Ok(Arch::Wormhole),
+            0xb140 => Ok(Arch::Blackhole),
+            id => Err(id),
+        }
+    }
+
     pub fn is_wormhole(&self) -> bool {
         matches!(self, Arch::Wormhole)
     }
@@ -24,6 +34,16 @@ impl Arch {
     pub fn is_blackhole(&self) -> bool {
         matches!(self, Arch::Blackhole)
     }
+
+    /// The size of the noc grid as (x, y), or None for an unknown arch.
+    pub fn grid_size(&self) -> Option<(u8, u8)> {
+        match self {
+            Arch::Grayskull => Some((13, 12)),
+            Arch::Wormhole => Some((10, 12)),
+            Arch::Blackhole => Some((17, 12)),
+            Arch::Unknown(_) => None,
+        }
+    }
 }
 
 impl FromStr for Arch {
@@ -49,3 +69,16 @@ impl fmt::Display for Arch {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Arch;
+
+    #[test]
+    fn arch_from_device_id() {
+        assert_eq!(Arch::from_device_id(0xfaca), Ok(Arch::Grayskull));
+        assert_eq!(Arch::from_device_id(0x401e), Ok(Arch::Wormhole));
+        assert_eq!(Arch::from_device_id(0xb140), Ok(Arch::Blackhole));
+        assert_eq!(Arch::from_device_id(0x1234), Err(0x1234));
+    }
+}
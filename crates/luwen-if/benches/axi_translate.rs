@@ -0,0 +1,24 @@
+// SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use luwen_if::chip::{axi_translate, communication::chip_comms::load_axi_table};
+
+fn axi_translate_bench(c: &mut Criterion) {
+    // Wormhole uses a flat table while blackhole uses a tree that is walked one path segment at a time.
+    let wormhole = load_axi_table("wormhole-axi-pci.bin", 0);
+    let blackhole = load_axi_table("blackhole-axi-pci.bin", 0);
+
+    c.bench_function("axi_translate flat", |b| {
+        b.iter(|| axi_translate(Some(&wormhole), black_box("ARC_RESET.SCRATCH[0]")).unwrap())
+    });
+
+    c.bench_function("axi_translate tree", |b| {
+        b.iter(|| {
+            axi_translate(Some(&blackhole), black_box("arc_ss.reset_unit.SCRATCH_0")).unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, axi_translate_bench);
+criterion_main!(benches);
@@ -15,7 +15,7 @@ pub enum PowerState {
     LongIdle,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ArcState {
     A0,
     A1,
@@ -23,6 +23,22 @@ pub enum ArcState {
     A5,
 }
 
+impl std::str::FromStr for ArcState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "A0" => Ok(ArcState::A0),
+            "A1" => Ok(ArcState::A1),
+            "A3" => Ok(ArcState::A3),
+            "A5" => Ok(ArcState::A5),
+            _ => Err(format!(
+                "Unknown arc state {s:?}; expected one of A0, A1, A3 or A5"
+            )),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum FwType {
     ArcL2,
@@ -84,6 +84,40 @@ impl Default for EthCore {
     }
 }
 
+/// The number of enabled units of each resource on a blackhole chip.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResourceLayout {
+    /// Tensix columns that have not been harvested.
+    pub tensix_cols: u32,
+    /// Ethernet ports that are reported as live.
+    pub eth: u32,
+    /// GDDR channels that finished training.
+    pub gddr: u32,
+    /// L2CPU cores that report a nonzero clock.
+    pub l2cpu: u32,
+    /// PCIe instances, None when the firmware does not report them in telemetry.
+    pub pcie: Option<u32>,
+}
+
+impl ResourceLayout {
+    pub fn from_telemetry(telemetry: &super::Telemetry) -> Self {
+        let harvesting =
+            crate::HarvestingMask::new(luwen_core::Arch::Blackhole, telemetry.harvesting_state)
+                .unwrap();
+
+        Self {
+            tensix_cols: harvesting.enabled_columns().len() as u32,
+            // One live bit per ethernet port, there are 14 ports.
+            eth: (telemetry.eth_status0 & 0x3fff).count_ones(),
+            gddr: (0..telemetry.gddr_channel_count())
+                .filter(|&channel| telemetry.gddr_channel_trained(channel))
+                .count() as u32,
+            l2cpu: telemetry.l2cpu_enabled_count(),
+            pcie: None,
+        }
+    }
+}
+
 struct SpiBuffer {
     addr: u32,
     size: u32,
@@ -287,6 +321,11 @@ impl Blackhole {
         })
     }
 
+    /// Decode the enabled tensix columns, ethernet ports, gddr channels and l2cpus from telemetry.
+    pub fn resource_layout(&self) -> Result<ResourceLayout, PlatformError> {
+        Ok(ResourceLayout::from_telemetry(&self.get_telemetry()?))
+    }
+
     pub fn spi_write(&self, mut addr: u32, value: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
         let buffer = self.get_spi_buffer()?;
 
@@ -337,15 +376,6 @@ impl Blackhole {
 
         Ok(())
     }
-
-    pub fn get_local_chip_coord(&self) -> Result<EthAddr, PlatformError> {
-        Ok(EthAddr {
-            rack_x: 0,
-            rack_y: 0,
-            shelf_x: 0,
-            shelf_y: 0,
-        })
-    }
 }
 
 fn default_status() -> InitStatus {
@@ -437,6 +467,15 @@ impl ChipImpl for Blackhole {
     }
 
     fn arc_msg(&self, msg: ArcMsgOptions) -> Result<ArcMsgOk, PlatformError> {
+        // A request that timed out waiting for its response may still be answered later,
+        // resending it could then pick up the stale response.
+        if msg.retries > 0 {
+            return Err(PlatformError::Generic(
+                "Blackhole arc messages can't be retried".to_string(),
+                BtWrapper::capture(),
+            ));
+        }
+
         let code = msg.msg.msg_code();
         let args = msg.msg.args();
 
@@ -493,7 +532,10 @@ impl ChipImpl for Blackhole {
         )?;
 
         // Parse telemetry data
-        let mut telemetry_data = super::Telemetry::default();
+        let mut telemetry_data = super::Telemetry {
+            arch: Some(luwen_core::Arch::Blackhole),
+            ..Default::default()
+        };
         for i in 0..entry_count as u8 {
             let entry = u32_from_slice(&telemetry_tags_data_block, i);
             let tag = entry & 0xFF;
@@ -547,4 +589,19 @@ impl ChipImpl for Blackhole {
     fn get_device_info(&self) -> Result<Option<crate::DeviceInfo>, PlatformError> {
         Ok(self.chip_if.get_device_info()?)
     }
+
+    fn get_local_chip_coord(&self) -> Result<EthAddr, PlatformError> {
+        Ok(EthAddr {
+            rack_x: 0,
+            rack_y: 0,
+            shelf_x: 0,
+            shelf_y: 0,
+        })
+    }
+
+    fn get_harvesting(&self) -> Result<crate::HarvestingMask, PlatformError> {
+        // The harvested tensix columns are reported through telemetry rather than an arc message.
+        let mask = self.get_telemetry()?.harvesting_state;
+        Ok(crate::HarvestingMask::new(luwen_core::Arch::Blackhole, mask).unwrap())
+    }
 }
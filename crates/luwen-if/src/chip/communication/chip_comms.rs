@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use super::chip_interface::ChipInterface;
+use crate::BroadcastConfig;
 
 #[derive(Error, Debug)]
 pub enum AxiError {
@@ -68,6 +69,8 @@ pub fn load_axi_table(file: &str, _version: u32) -> MemorySlices {
 pub trait ChipComms {
     /// Translate a String path into the corresponding AXI address.
     fn axi_translate(&self, addr: &str) -> Result<AxiData, AxiError>;
+    /// List every (name, addr, size) in the AXI table, each name can be passed to `axi_translate`.
+    fn axi_symbols(&self) -> Result<Vec<(String, u64, u64)>, AxiError>;
     /// Read and write to the NOC using AXI address gotten from `axi_translate`.
     fn axi_read(
         &self,
@@ -107,9 +110,61 @@ pub trait ChipComms {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>>;
 
+    /// Replace the bits of the 32 bit register selected by `mask` with those of `value`, returning the written value.
+    /// The read and the write are done in a single transport access so that no other user of the
+    /// same device handle can touch the chip in between.
+    fn axi_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>>;
+    #[allow(clippy::too_many_arguments)]
+    fn noc_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>>;
+
+    /// Read the 4 bytes at `addr` exactly as they are laid out on the device.
+    fn noc_read32_raw(
+        &self,
+        chip_if: &dyn ChipInterface,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+    ) -> Result<[u8; 4], Box<dyn std::error::Error>> {
+        let mut value = [0; 4];
+        self.noc_read(chip_if, noc_id, x, y, addr, &mut value)?;
+        Ok(value)
+    }
+
+    /// Write 4 bytes to `addr` exactly as they should be laid out on the device.
+    fn noc_write32_raw(
+        &self,
+        chip_if: &dyn ChipInterface,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        value: [u8; 4],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.noc_write(chip_if, noc_id, x, y, addr, value.as_slice())
+    }
+
     /// Convenience functions for reading and writing 32 bit values.
+    /// The device is little endian, so the value is converted from/to little endian bytes
+    /// regardless of the host byte order, use the `_raw` variants to get the bytes unchanged.
     fn noc_read32(
         &self,
         chip_if: &dyn ChipInterface,
@@ -118,9 +173,9 @@ pub trait ChipComms {
         y: u8,
         addr: u64,
     ) -> Result<u32, Box<dyn std::error::Error>> {
-        let mut value = [0; 4];
-        self.noc_read(chip_if, noc_id, x, y, addr, &mut value)?;
-        Ok(u32::from_le_bytes(value))
+        Ok(u32::from_le_bytes(
+            self.noc_read32_raw(chip_if, noc_id, x, y, addr)?,
+        ))
     }
 
     fn noc_write32(
@@ -132,7 +187,7 @@ pub trait ChipComms {
         addr: u64,
         value: u32,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        self.noc_write(chip_if, noc_id, x, y, addr, value.to_le_bytes().as_slice())
+        self.noc_write32_raw(chip_if, noc_id, x, y, addr, value.to_le_bytes())
     }
 
     fn noc_broadcast32(
@@ -142,7 +197,13 @@ pub trait ChipComms {
         addr: u64,
         value: u32,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        self.noc_broadcast(chip_if, noc_id, addr, value.to_le_bytes().as_slice())
+        self.noc_broadcast(
+            chip_if,
+            noc_id,
+            addr,
+            value.to_le_bytes().as_slice(),
+            &BroadcastConfig::default(),
+        )
     }
 
     fn axi_read32(
@@ -266,6 +327,53 @@ pub fn axi_translate(data: Option<&MemorySlices>, addr: &str) -> Result<AxiData,
     }
 }
 
+fn axi_symbols_tree(
+    data: &std::collections::HashMap<String, MemorySlice>,
+    prefix: &str,
+    base: u64,
+    output: &mut Vec<(String, u64, u64)>,
+) {
+    let mut names: Vec<_> = data.keys().collect();
+    names.sort();
+
+    for name in names {
+        let slice = &data[name];
+        let name = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}.{name}")
+        };
+
+        let elements = match slice.array_count {
+            Some(count) => (0..count)
+                .map(|index| (format!("{name}[{index}]"), slice.size * index))
+                .collect(),
+            None => vec![(name, 0)],
+        };
+        for (name, element_offset) in elements {
+            let addr = base + slice.offset + element_offset;
+            output.push((name.clone(), addr, slice.size));
+            axi_symbols_tree(&slice.children, &name, addr, output);
+        }
+    }
+}
+
+/// List every (name, addr, size) that `axi_translate` can resolve in the table, array elements are listed individually.
+pub fn axi_symbols(data: Option<&MemorySlices>) -> Result<Vec<(String, u64, u64)>, AxiError> {
+    let mut output = Vec::new();
+    match data.ok_or(AxiError::NoAxiData)? {
+        MemorySlices::Flat(data) => {
+            for (name, data) in data {
+                output.push((name.clone(), data.addr, data.size));
+            }
+            output.sort();
+        }
+        MemorySlices::Tree(data) => axi_symbols_tree(data, "", 0, &mut output),
+    }
+
+    Ok(output)
+}
+
 pub struct ArcIf {
     pub axi_data: MemorySlices,
 }
@@ -275,6 +383,10 @@ impl ChipComms for ArcIf {
         axi_translate(Some(&self.axi_data), addr)
     }
 
+    fn axi_symbols(&self) -> Result<Vec<(String, u64, u64)>, AxiError> {
+        axi_symbols(Some(&self.axi_data))
+    }
+
     fn axi_read(
         &self,
         chip_if: &dyn ChipInterface,
@@ -323,8 +435,32 @@ impl ChipComms for ArcIf {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        chip_if.noc_broadcast(noc_id, addr, data)
+        chip_if.noc_broadcast(noc_id, addr, data, config)
+    }
+
+    fn axi_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        chip_if.axi_rmw32(addr as u32, mask, value)
+    }
+
+    fn noc_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        chip_if.noc_rmw32(noc_id, x, y, addr, mask, value)
     }
 }
 
@@ -340,6 +476,10 @@ impl ChipComms for NocIf {
         axi_translate(Some(&self.axi_data), addr)
     }
 
+    fn axi_symbols(&self) -> Result<Vec<(String, u64, u64)>, AxiError> {
+        axi_symbols(Some(&self.axi_data))
+    }
+
     fn axi_read(
         &self,
         chip_if: &dyn ChipInterface,
@@ -388,8 +528,32 @@ impl ChipComms for NocIf {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        chip_if.noc_broadcast(noc_id, addr, data)
+        chip_if.noc_broadcast(noc_id, addr, data, config)
+    }
+
+    fn axi_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        chip_if.noc_rmw32(self.noc_id, self.x, self.y, addr, mask, value)
+    }
+
+    fn noc_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        chip_if.noc_rmw32(noc_id, x, y, addr, mask, value)
     }
 }
 
@@ -398,6 +562,10 @@ impl ChipComms for Arc<dyn ChipComms> {
         self.as_ref().axi_translate(addr)
     }
 
+    fn axi_symbols(&self) -> Result<Vec<(String, u64, u64)>, AxiError> {
+        self.as_ref().axi_symbols()
+    }
+
     fn axi_read(
         &self,
         chip_if: &dyn ChipInterface,
@@ -446,8 +614,34 @@ impl ChipComms for Arc<dyn ChipComms> {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        self.as_ref().noc_broadcast(chip_if, noc_id, addr, data)
+        self.as_ref()
+            .noc_broadcast(chip_if, noc_id, addr, data, config)
+    }
+
+    fn axi_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        self.as_ref().axi_rmw32(chip_if, addr, mask, value)
+    }
+
+    fn noc_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        self.as_ref()
+            .noc_rmw32(chip_if, noc_id, x, y, addr, mask, value)
     }
 }
 
@@ -456,6 +650,10 @@ impl ChipComms for Arc<dyn ChipComms + Send + Sync> {
         self.as_ref().axi_translate(addr)
     }
 
+    fn axi_symbols(&self) -> Result<Vec<(String, u64, u64)>, AxiError> {
+        self.as_ref().axi_symbols()
+    }
+
     fn axi_read(
         &self,
         chip_if: &dyn ChipInterface,
@@ -504,7 +702,57 @@ impl ChipComms for Arc<dyn ChipComms + Send + Sync> {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        self.as_ref().noc_broadcast(chip_if, noc_id, addr, data)
+        self.as_ref()
+            .noc_broadcast(chip_if, noc_id, addr, data, config)
+    }
+
+    fn axi_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        self.as_ref().axi_rmw32(chip_if, addr, mask, value)
+    }
+
+    fn noc_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        self.as_ref()
+            .noc_rmw32(chip_if, noc_id, x, y, addr, mask, value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{axi_symbols, axi_translate, load_axi_table};
+
+    #[test]
+    fn axi_symbols_round_trip() {
+        for file in [
+            "grayskull-axi-pci.bin",
+            "wormhole-axi-pci.bin",
+            "wormhole-axi-noc.bin",
+            "blackhole-axi-pci.bin",
+        ] {
+            let table = load_axi_table(file, 0);
+            let symbols = axi_symbols(Some(&table)).unwrap();
+            assert!(!symbols.is_empty(), "{file} has no symbols");
+
+            for (name, addr, size) in symbols {
+                let data = axi_translate(Some(&table), &name).unwrap();
+                assert_eq!((data.addr, data.size), (addr, size), "{file}: {name}");
+            }
+        }
     }
 }
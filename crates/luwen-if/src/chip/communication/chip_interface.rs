@@ -3,7 +3,7 @@
 
 use std::sync::Arc;
 
-use crate::{DeviceInfo, EthAddr};
+use crate::{BroadcastConfig, DeviceInfo, EthAddr};
 
 /// This trait is used to abstract the interface to the lowest level
 /// chip communication primatives. These primatives are defined to be a chip resource
@@ -42,6 +42,7 @@ pub trait ChipInterface: 'static {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>>;
 
     /// Read and write to a noc endpoint via ethernet on a local or remote chip.
@@ -69,8 +70,77 @@ pub trait ChipInterface: 'static {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>>;
 
+    /// Read a 32 bit register, replace the bits selected by `mask` with those of `value` and
+    /// write the result back, returning the written value.
+    /// Transports that serialize access to the chip should override these so that the read and the
+    /// write happen under a single acquisition, the default implementations are two separate accesses.
+    fn axi_rmw32(
+        &self,
+        addr: u32,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        let mut data = [0u8; 4];
+        self.axi_read(addr, &mut data)?;
+        let value = (u32::from_le_bytes(data) & !mask) | (value & mask);
+        self.axi_write(addr, &value.to_le_bytes())?;
+
+        Ok(value)
+    }
+    fn noc_rmw32(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        let mut data = [0u8; 4];
+        self.noc_read(noc_id, x, y, addr, &mut data)?;
+        let value = (u32::from_le_bytes(data) & !mask) | (value & mask);
+        self.noc_write(noc_id, x, y, addr, &value.to_le_bytes())?;
+
+        Ok(value)
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn eth_noc_rmw32(
+        &self,
+        eth_addr: EthAddr,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        let mut data = [0u8; 4];
+        self.eth_noc_read(eth_addr, noc_id, x, y, addr, &mut data)?;
+        let value = (u32::from_le_bytes(data) & !mask) | (value & mask);
+        self.eth_noc_write(eth_addr, noc_id, x, y, addr, &value.to_le_bytes())?;
+
+        Ok(value)
+    }
+
+    /// Same as eth_noc_read but the read fails after `timeout` rather than the ethernet timeout of the transport.
+    /// Transports that do not have a configurable ethernet timeout ignore `timeout`.
+    #[allow(clippy::too_many_arguments)]
+    fn eth_noc_read_timeout(
+        &self,
+        eth_addr: EthAddr,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        data: &mut [u8],
+        _timeout: std::time::Duration,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.eth_noc_read(eth_addr, noc_id, x, y, addr, data)
+    }
+
     fn as_any(&self) -> &dyn std::any::Any;
 }
 
@@ -114,8 +184,9 @@ impl ChipInterface for Arc<dyn ChipInterface + Send + Sync> {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        self.as_ref().noc_broadcast(noc_id, addr, data)
+        self.as_ref().noc_broadcast(noc_id, addr, data, config)
     }
 
     fn eth_noc_read(
@@ -150,9 +221,59 @@ impl ChipInterface for Arc<dyn ChipInterface + Send + Sync> {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
         self.as_ref()
-            .eth_noc_broadcast(eth_addr, noc_id, addr, data)
+            .eth_noc_broadcast(eth_addr, noc_id, addr, data, config)
+    }
+
+    fn axi_rmw32(
+        &self,
+        addr: u32,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        self.as_ref().axi_rmw32(addr, mask, value)
+    }
+
+    fn noc_rmw32(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        self.as_ref().noc_rmw32(noc_id, x, y, addr, mask, value)
+    }
+
+    fn eth_noc_rmw32(
+        &self,
+        eth_addr: EthAddr,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        self.as_ref()
+            .eth_noc_rmw32(eth_addr, noc_id, x, y, addr, mask, value)
+    }
+
+    fn eth_noc_read_timeout(
+        &self,
+        eth_addr: EthAddr,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        data: &mut [u8],
+        timeout: std::time::Duration,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.as_ref()
+            .eth_noc_read_timeout(eth_addr, noc_id, x, y, addr, data, timeout)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -210,8 +331,9 @@ impl ChipInterface for NocInterface {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        self.backing.noc_broadcast(noc_id, addr, data)
+        self.backing.noc_broadcast(noc_id, addr, data, config)
     }
 
     fn eth_noc_read(
@@ -246,8 +368,60 @@ impl ChipInterface for NocInterface {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        self.backing.eth_noc_broadcast(eth_addr, noc_id, addr, data)
+        self.backing
+            .eth_noc_broadcast(eth_addr, noc_id, addr, data, config)
+    }
+
+    fn axi_rmw32(
+        &self,
+        addr: u32,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        self.backing
+            .noc_rmw32(self.noc_id, self.x, self.y, addr as u64, mask, value)
+    }
+
+    fn noc_rmw32(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        self.backing.noc_rmw32(noc_id, x, y, addr, mask, value)
+    }
+
+    fn eth_noc_rmw32(
+        &self,
+        eth_addr: EthAddr,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        self.backing
+            .eth_noc_rmw32(eth_addr, noc_id, x, y, addr, mask, value)
+    }
+
+    fn eth_noc_read_timeout(
+        &self,
+        eth_addr: EthAddr,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        data: &mut [u8],
+        timeout: std::time::Duration,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.backing
+            .eth_noc_read_timeout(eth_addr, noc_id, x, y, addr, data, timeout)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -117,6 +117,7 @@ impl Chip {
                 Arch::Blackhole => Box::new(Self::bh_open(arch, backend)?),
                 _ => panic!("Unsupported chip"),
             },
+            init_status: None,
         })
     }
 }
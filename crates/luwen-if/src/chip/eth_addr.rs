@@ -1,7 +1,7 @@
 // SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use crate::error::PlatformError;
 
@@ -105,3 +105,56 @@ impl Display for EthAddr {
         ))
     }
 }
+
+/// Parses the format written by `Display`, "[rack_x, rack_y, shelf_x, shelf_y]".
+/// This is the same order as the tuple accepted by `IntoChip`, the brackets are optional.
+impl FromStr for EthAddr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s.trim();
+        let inner = inner
+            .strip_prefix('[')
+            .and_then(|v| v.strip_suffix(']'))
+            .unwrap_or(inner);
+
+        let values = inner
+            .split(',')
+            .map(|v| v.trim().parse::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| format!("Could not parse eth addr {s:?}: {err}"))?;
+
+        match values.as_slice() {
+            &[rack_x, rack_y, shelf_x, shelf_y] => Ok(EthAddr {
+                rack_x,
+                rack_y,
+                shelf_x,
+                shelf_y,
+            }),
+            _ => Err(format!(
+                "Could not parse eth addr {s:?}: expected 4 values, found {}",
+                values.len()
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EthAddr;
+
+    #[test]
+    fn eth_addr_round_trip() {
+        let addr = EthAddr {
+            rack_x: 1,
+            rack_y: 2,
+            shelf_x: 3,
+            shelf_y: 4,
+        };
+
+        assert_eq!(addr.to_string().parse::<EthAddr>(), Ok(addr));
+        assert_eq!("1,2,3,4".parse::<EthAddr>(), Ok(addr));
+        assert!("[1, 2, 3]".parse::<EthAddr>().is_err());
+        assert!("[1, 2, 3, 256]".parse::<EthAddr>().is_err());
+    }
+}
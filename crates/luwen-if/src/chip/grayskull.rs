@@ -588,23 +588,25 @@ impl ChipImpl for Grayskull {
     }
 
     fn arc_msg(&self, msg: ArcMsgOptions) -> Result<ArcMsgOk, PlatformError> {
-        let (msg_reg, return_reg) = if msg.use_second_mailbox {
-            return Err(ArcMsgProtocolError::InvalidMailbox(2).into_error())?;
-        } else {
-            (5, 3)
-        };
-
-        self.check_arc_msg_safe(msg_reg, return_reg)?;
-
-        crate::arc_msg::arc_msg(
-            self,
-            &msg.msg,
-            msg.wait_for_done,
-            msg.timeout,
-            msg_reg,
-            return_reg,
-            msg.addrs.as_ref().unwrap_or(&self.arc_addrs),
-        )
+        super::arc_msg_with_retries(&msg, |msg| {
+            let (msg_reg, return_reg) = if msg.use_second_mailbox {
+                return Err(ArcMsgProtocolError::InvalidMailbox(2).into_error())?;
+            } else {
+                (5, 3)
+            };
+
+            self.check_arc_msg_safe(msg_reg, return_reg)?;
+
+            crate::arc_msg::arc_msg(
+                self,
+                &msg.msg,
+                msg.wait_for_done,
+                msg.timeout,
+                msg_reg,
+                return_reg,
+                msg.addrs.as_ref().unwrap_or(&self.arc_addrs),
+            )
+        })
     }
 
     fn get_neighbouring_chips(&self) -> Result<Vec<NeighbouringChip>, PlatformError> {
@@ -759,6 +761,7 @@ impl ChipImpl for Grayskull {
         //         .axi_read32(&self.chip_if, telemetry_struct_offset + (5 * 4))? as u64;
 
         Ok(super::Telemetry {
+            arch: Some(Arch::Grayskull),
             board_id: ((board_id_high as u64) << 32) | (board_id_low as u64),
             enum_version,
             device_id,
@@ -800,6 +803,8 @@ impl ChipImpl for Grayskull {
             rt_seconds,
             tt_flash_version,
             fw_bundle_version,
+            // On wh/gs the arc0 health counter is the arc heartbeat.
+            timer_heartbeat: arc0_health,
             ..Default::default()
         })
     }
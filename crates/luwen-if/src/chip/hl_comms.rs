@@ -1,7 +1,7 @@
 // SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::error::PlatformError;
+use crate::{error::PlatformError, BroadcastConfig};
 
 use super::{AxiData, AxiError, ChipComms, ChipInterface};
 
@@ -38,11 +38,49 @@ pub trait HlComms {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.noc_broadcast_with_config(noc_id, addr, data, &BroadcastConfig::default())
+    }
+
+    /// Broadcast to the tiles selected by `config`.
+    /// Grayskull only supports the default config, see `BroadcastConfig`.
+    fn noc_broadcast_with_config(
+        &self,
+        noc_id: u8,
+        addr: u64,
+        data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
         let (arc_if, chip_if) = self.comms_obj();
-        arc_if.noc_broadcast(chip_if, noc_id, addr, data)
+        arc_if.noc_broadcast(chip_if, noc_id, addr, data, config)
     }
 
+    /// Read the 4 bytes at `addr` in device order, without converting them to a u32.
+    fn noc_read32_raw(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+    ) -> Result<[u8; 4], Box<dyn std::error::Error>> {
+        let (arc_if, chip_if) = self.comms_obj();
+        arc_if.noc_read32_raw(chip_if, noc_id, x, y, addr)
+    }
+
+    /// Write 4 bytes to `addr` in device order.
+    fn noc_write32_raw(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        value: [u8; 4],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (arc_if, chip_if) = self.comms_obj();
+        arc_if.noc_write32_raw(chip_if, noc_id, x, y, addr, value)
+    }
+
+    /// Read a little endian u32, the device byte order, and convert it to the host byte order.
     fn noc_read32(
         &self,
         noc_id: u8,
@@ -54,6 +92,7 @@ pub trait HlComms {
         arc_if.noc_read32(chip_if, noc_id, x, y, addr)
     }
 
+    /// Write `value` to `addr` as a little endian u32.
     fn noc_write32(
         &self,
         noc_id: u8,
@@ -66,6 +105,35 @@ pub trait HlComms {
         arc_if.noc_write32(chip_if, noc_id, x, y, addr, value)
     }
 
+    /// Read a little endian u64, `addr` must be 8 byte aligned.
+    fn noc_read64(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        check_align64(addr)?;
+
+        let mut value = [0u8; 8];
+        self.noc_read(noc_id, x, y, addr, &mut value)?;
+        Ok(u64::from_le_bytes(value))
+    }
+
+    /// Write a little endian u64, `addr` must be 8 byte aligned.
+    fn noc_write64(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        value: u64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        check_align64(addr)?;
+
+        self.noc_write(noc_id, x, y, addr, &value.to_le_bytes())
+    }
+
     fn noc_broadcast32(
         &self,
         noc_id: u8,
@@ -95,6 +163,45 @@ pub trait HlComms {
         let (arc_if, chip_if) = self.comms_obj();
         arc_if.axi_write32(chip_if, addr, value)
     }
+
+    /// Read a u32, replace the bits selected by `mask` with those bits of `value` and write it back.
+    /// Returns the value that was written. The read and the write happen under a single access to the
+    /// transport, so no other user of the same device handle can write the register in between.
+    fn noc_rmw32(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        let (arc_if, chip_if) = self.comms_obj();
+        arc_if.noc_rmw32(chip_if, noc_id, x, y, addr, mask, value)
+    }
+
+    /// Read a u32, replace the bits selected by `mask` with those bits of `value` and write it back.
+    /// Returns the value that was written. The read and the write happen under a single access to the
+    /// transport, so no other user of the same device handle can write the register in between.
+    fn axi_rmw32(
+        &self,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        let (arc_if, chip_if) = self.comms_obj();
+        arc_if.axi_rmw32(chip_if, addr, mask, value)
+    }
+}
+
+fn check_align64(addr: u64) -> Result<(), String> {
+    if addr & 0x7 != 0 {
+        return Err(format!(
+            "64-bit noc access to {addr:#x} is not 8 byte aligned"
+        ));
+    }
+
+    Ok(())
 }
 
 #[inline]
@@ -225,6 +332,12 @@ pub trait HlCommsInterface: HlComms {
         arc_if.axi_translate(addr.as_ref())
     }
 
+    fn axi_symbols(&self) -> Result<Vec<(String, u64, u64)>, AxiError> {
+        let (arc_if, _) = self.comms_obj();
+
+        arc_if.axi_symbols()
+    }
+
     fn axi_read_field<'a>(
         &self,
         addr: &AxiData,
@@ -5,7 +5,7 @@ use std::convert::Infallible;
 
 use crate::{error::PlatformError, ChipImpl};
 
-use status::InitStatus;
+use status::{InitFailureReason, InitStatus};
 
 pub mod status;
 
@@ -39,12 +39,43 @@ impl From<InitError<Infallible>> for PlatformError {
     }
 }
 
+/// Which component failures a chip is allowed to finish initialization with.
+/// A failure that is not tolerated is returned as an error from wait_for_init.
+#[derive(Clone, Copy, Debug)]
+pub struct FailureTolerance {
+    /// Also covers losing communication with the chip.
+    pub arc: bool,
+    pub dram: bool,
+    pub eth: bool,
+}
+
+impl Default for FailureTolerance {
+    fn default() -> Self {
+        Self {
+            arc: true,
+            dram: true,
+            eth: true,
+        }
+    }
+}
+
+impl FailureTolerance {
+    pub fn allows(&self, reason: InitFailureReason) -> bool {
+        match reason {
+            InitFailureReason::PcieDown | InitFailureReason::ArcHang => self.arc,
+            InitFailureReason::DramNotTrained => self.dram,
+            InitFailureReason::EthTimeout => self.eth,
+        }
+    }
+}
+
 /// This function will wait for the chip to be initialized.
 /// It will return Ok(true) if the chip initialized successfully.
 /// It will return Ok(false) if the chip failed to initialize, but we can continue running.
 ///     - This is only possible if allow_failure is true.
 /// An Err(..) will be returned if the chip failed to initialize and we cannot continue running the chip detection sequence.
 ///     - In the case that allow_failure is false, Ok(true) will be returned as an error.
+/// Regardless of allow_failure, if a component failed and that failure is not part of tolerance an Err(..) is returned.
 ///
 /// This component makes a callback available which allows the init status to be updated if there
 /// is someone/something monitoring the init progress. The initial/driving purpose of this is to
@@ -54,7 +85,18 @@ pub fn wait_for_init<E>(
     callback: &mut impl FnMut(ChipDetectState) -> Result<(), E>,
     allow_failure: bool,
     noc_safe: bool,
+    tolerance: FailureTolerance,
 ) -> Result<InitStatus, InitError<E>> {
+    let check_tolerance = |status: &InitStatus| -> Result<(), PlatformError> {
+        match status.failure_reason() {
+            Some(reason) if !tolerance.allows(reason) => Err(PlatformError::Generic(
+                format!("Chip initialization failed: {reason} is not tolerated \n{status}"),
+                crate::error::BtWrapper::capture(),
+            )),
+            _ => Ok(()),
+        }
+    };
+
     // We want to make sure that we always call the callback at least once so that the caller can mark the chip presence.
     callback(ChipDetectState {
         chip,
@@ -78,6 +120,7 @@ pub fn wait_for_init<E>(
                         crate::error::BtWrapper(bt_tracker),
                     ))?;
                 } else {
+                    check_tolerance(&status)?;
                     callback(ChipDetectState {
                         chip,
                         call: CallReason::ChipInitCompleted(&status),
@@ -100,6 +143,8 @@ pub fn wait_for_init<E>(
         let call = if !status.init_complete() {
             CallReason::InitWait(&status)
         } else {
+            check_tolerance(&status)?;
+
             // Yes, this also returns a result that we are ignoring.
             // But we are always going to return right after this anyway.
             callback(ChipDetectState {
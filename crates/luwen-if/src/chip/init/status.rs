@@ -369,6 +369,26 @@ impl fmt::Display for CommsStatus {
     }
 }
 
+/// Broad category of why a chip failed to initialize.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InitFailureReason {
+    PcieDown,
+    ArcHang,
+    EthTimeout,
+    DramNotTrained,
+}
+
+impl fmt::Display for InitFailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitFailureReason::PcieDown => f.write_str("PcieDown"),
+            InitFailureReason::ArcHang => f.write_str("ArcHang"),
+            InitFailureReason::EthTimeout => f.write_str("EthTimeout"),
+            InitFailureReason::DramNotTrained => f.write_str("DramNotTrained"),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct InitStatus {
     pub comms_status: CommsStatus,
@@ -469,6 +489,32 @@ impl InitStatus {
         !self.is_waiting()
     }
 
+    /// Categorize the init failure by the first component to fail, in the order they are brought up.
+    /// Returns None if init has not failed (or the chip was never checked);
+    /// cpu failures are not categorized.
+    pub fn failure_reason(&self) -> Option<InitFailureReason> {
+        fn failed<P, E>(status: &ComponentStatusInfo<P, E>) -> bool {
+            status
+                .wait_status
+                .iter()
+                .any(|v| matches!(v, WaitStatus::Error(_) | WaitStatus::Timeout(_)))
+        }
+
+        if self.unknown_state {
+            None
+        } else if !self.comms_status.ok() {
+            Some(InitFailureReason::PcieDown)
+        } else if failed(&self.arc_status) {
+            Some(InitFailureReason::ArcHang)
+        } else if failed(&self.dram_status) {
+            Some(InitFailureReason::DramNotTrained)
+        } else if failed(&self.eth_status) {
+            Some(InitFailureReason::EthTimeout)
+        } else {
+            None
+        }
+    }
+
     pub fn has_error(&self) -> bool {
         !self.comms_status.ok()
             || self.arc_status.has_error()
@@ -477,3 +523,100 @@ impl InitStatus {
             || self.cpu_status.has_error()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        ArcInitError, CommsStatus, DramChannelStatus, DramInitError, EthernetInitError,
+        EthernetPartialInitError, InitFailureReason, InitStatus, WaitStatus,
+    };
+    use std::convert::Infallible;
+
+    fn status(
+        comms_ok: bool,
+        arc: WaitStatus<Infallible, ArcInitError>,
+        dram: WaitStatus<Infallible, DramInitError>,
+        eth: WaitStatus<EthernetPartialInitError, EthernetInitError>,
+    ) -> InitStatus {
+        let mut status = InitStatus::new_unknown();
+        status.unknown_state = false;
+        if comms_ok {
+            status.comms_status = CommsStatus::CanCommunicate;
+        }
+        status.arc_status.wait_status = Box::new([arc]);
+        status.dram_status.wait_status = Box::new([dram]);
+        status.eth_status.wait_status = Box::new([eth]);
+
+        status
+    }
+
+    #[test]
+    fn failure_reason_order() {
+        let timeout = std::time::Duration::from_secs(1);
+        let dram_error =
+            || WaitStatus::Error(DramInitError::NotTrained(DramChannelStatus::TrainingFail));
+
+        let cases = [
+            (
+                status(true, WaitStatus::Done, WaitStatus::Done, WaitStatus::Done),
+                None,
+            ),
+            (
+                status(false, WaitStatus::Done, WaitStatus::Done, WaitStatus::Done),
+                Some(InitFailureReason::PcieDown),
+            ),
+            (
+                status(
+                    false,
+                    WaitStatus::Error(ArcInitError::Hung),
+                    dram_error(),
+                    WaitStatus::Timeout(timeout),
+                ),
+                Some(InitFailureReason::PcieDown),
+            ),
+            (
+                status(
+                    true,
+                    WaitStatus::Timeout(timeout),
+                    dram_error(),
+                    WaitStatus::Done,
+                ),
+                Some(InitFailureReason::ArcHang),
+            ),
+            (
+                status(
+                    true,
+                    WaitStatus::Done,
+                    dram_error(),
+                    WaitStatus::Error(EthernetInitError::NotTrained),
+                ),
+                Some(InitFailureReason::DramNotTrained),
+            ),
+            (
+                status(
+                    true,
+                    WaitStatus::Done,
+                    WaitStatus::Done,
+                    WaitStatus::Timeout(timeout),
+                ),
+                Some(InitFailureReason::EthTimeout),
+            ),
+            // Components that were not checked or are still coming up have not failed.
+            (
+                status(
+                    true,
+                    WaitStatus::NoCheck,
+                    WaitStatus::Waiting(None),
+                    WaitStatus::NotPresent,
+                ),
+                None,
+            ),
+        ];
+
+        for (i, (status, expected)) in cases.iter().enumerate() {
+            assert_eq!(status.failure_reason(), *expected, "case {i}");
+        }
+
+        assert_eq!(InitStatus::new_unknown().failure_reason(), None);
+    }
+}
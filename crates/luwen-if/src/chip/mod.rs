@@ -12,23 +12,24 @@ mod remote;
 mod spi;
 mod wormhole;
 
-pub use blackhole::{message::MessageError, Blackhole};
+pub use blackhole::{message::MessageError, Blackhole, ResourceLayout};
 pub use communication::chip_comms::{
-    axi_translate, ArcIf, AxiData, AxiError, ChipComms, MemorySlice, MemorySlices,
+    axi_symbols, axi_translate, ArcIf, AxiData, AxiError, ChipComms, MemorySlice, MemorySlices,
 };
 pub use communication::chip_interface::{ChipInterface, NocInterface};
 pub use grayskull::Grayskull;
 pub use hl_comms::{HlComms, HlCommsInterface};
 pub use init::status::InitStatus;
 pub use init::{
-    status::{CommsStatus, ComponentStatusInfo},
-    wait_for_init, CallReason, ChipDetectState, InitError,
+    status::{CommsStatus, ComponentStatusInfo, InitFailureReason, WaitStatus},
+    wait_for_init, CallReason, ChipDetectState, FailureTolerance, InitError,
 };
 use luwen_core::Arch;
+pub use spi::crc32_update;
 pub use wormhole::Wormhole;
 
-use crate::arc_msg::TypedArcMsg;
 pub use crate::arc_msg::{ArcMsg, ArcMsgOk};
+use crate::arc_msg::{ArcMsgError, ArcMsgProtocolError, TypedArcMsg};
 use crate::{arc_msg::ArcMsgAddr, error::PlatformError, DeviceInfo};
 
 /// Arc message interface
@@ -39,6 +40,9 @@ pub struct ArcMsgOptions {
     pub timeout: std::time::Duration,
     pub use_second_mailbox: bool,
     pub addrs: Option<ArcMsgAddr>,
+    /// Number of times to resend the message if ARC times out responding to it.
+    /// Only supported on grayskull and wormhole, blackhole returns an error if this is non-zero.
+    pub retries: u32,
 }
 
 impl Default for ArcMsgOptions {
@@ -49,10 +53,47 @@ impl Default for ArcMsgOptions {
             timeout: std::time::Duration::from_secs(1),
             use_second_mailbox: false,
             addrs: None,
+            retries: 0,
         }
     }
 }
 
+/// Send an arc message, resending it with exponential backoff when ARC times out.
+/// If every attempt times out the error from the first attempt is returned.
+pub(crate) fn arc_msg_with_retries(
+    msg: &ArcMsgOptions,
+    mut send: impl FnMut(&ArcMsgOptions) -> Result<ArcMsgOk, PlatformError>,
+) -> Result<ArcMsgOk, PlatformError> {
+    fn is_timeout(result: &Result<ArcMsgOk, PlatformError>) -> bool {
+        matches!(
+            result,
+            Err(PlatformError::ArcMsgError(ArcMsgError::ProtocolError {
+                source: ArcMsgProtocolError::Timeout(_),
+                ..
+            }))
+        )
+    }
+
+    let result = send(msg);
+
+    let mut backoff = std::time::Duration::from_millis(10);
+    for _ in 0..msg.retries {
+        if !is_timeout(&result) {
+            break;
+        }
+
+        std::thread::sleep(backoff);
+        backoff *= 2;
+
+        let retry = send(msg);
+        if !is_timeout(&retry) {
+            return retry;
+        }
+    }
+
+    result
+}
+
 #[derive(Debug, Hash, PartialEq, Eq)]
 pub struct NeighbouringChip {
     pub local_noc_addr: (u8, u8),
@@ -60,8 +101,91 @@ pub struct NeighbouringChip {
     pub eth_addr: crate::EthAddr,
 }
 
+/// The result of `ChipImpl::health_check`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChipHealth {
+    /// The arc responded to a nop message.
+    pub arc_responsive: bool,
+    /// The arc scratch register could be read without returning 0xffffffff.
+    pub noc_responsive: bool,
+    /// Only set for chips accessed over ethernet, true if the scratch register could be read over the link.
+    pub eth_responsive: Option<bool>,
+}
+
+/// Static information about a board type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoardInfo {
+    pub name: &'static str,
+    pub arch: Arch,
+    pub asic_count: u8,
+    pub is_galaxy: bool,
+    pub has_ethernet: bool,
+}
+
+impl BoardInfo {
+    const fn new(name: &'static str, arch: Arch, asic_count: u8, has_ethernet: bool) -> Self {
+        Self {
+            name,
+            arch,
+            asic_count,
+            is_galaxy: false,
+            has_ethernet,
+        }
+    }
+
+    /// Decode the board type from a board serial number, returns None if it is unknown.
+    pub fn from_serial_number(serial_num: u64) -> Option<Self> {
+        let output = match (serial_num >> 36) & 0xFFFFF {
+            0x1 => match (serial_num >> 32) & 0xF {
+                0x2 => Self::new("E300_R2", Arch::Grayskull, 1, false),
+                0x3 | 0x4 => Self::new("E300_R3", Arch::Grayskull, 1, false),
+                _ => return None,
+            },
+            0x3 => Self::new("e150", Arch::Grayskull, 1, false),
+            0x7 => Self::new("e75", Arch::Grayskull, 1, false),
+            0x8 => Self::new("NEBULA_CB", Arch::Wormhole, 1, true),
+            0xA => Self::new("e300", Arch::Grayskull, 1, false),
+            0xB => Self {
+                is_galaxy: true,
+                ..Self::new("GALAXY", Arch::Wormhole, 1, true)
+            },
+            0x14 => Self::new("n300", Arch::Wormhole, 2, true),
+            0x18 => Self::new("n150", Arch::Wormhole, 1, true),
+            0x36 => Self::new("p100", Arch::Blackhole, 1, false),
+            0x40 => Self::new("p150A", Arch::Blackhole, 1, true),
+            _ => return None,
+        };
+
+        Some(output)
+    }
+}
+
+/// Local address of the noc0 register block in tensix and ethernet cores,
+/// the noc1 registers follow at `NOC_REGS_STRIDE`.
+const NOC_REGS_BASE: u64 = 0xFFB2_0000;
+const NOC_REGS_STRIDE: u64 = 0x1_0000;
+
+/// The throttler that each bit of `Telemetry::throttler` refers to, starting from bit 0.
+pub const THROTTLER_NAMES: [&str; 6] = [
+    "tdp",
+    "fast_tdc",
+    "tdc",
+    "thermal",
+    "board_power",
+    "gddr_thermal",
+];
+
+/// Firmware features that can be checked with `Telemetry::fw_feature`,
+/// along with the first firmware bundle version that has them.
+pub const FW_FEATURES: &[(&str, u32)] = &[
+    // 80.17.0.0 changed the tags used to lay out the boot_fs in spi.
+    ("boot_fs_tags_v2", 0x50_11_00_00),
+];
+
 #[derive(Default, Debug)]
 pub struct Telemetry {
+    /// The arch of the chip that reported this telemetry.
+    pub arch: Option<Arch>,
     pub board_id: u64,
     pub enum_version: u32,
     pub entry_count: u32,
@@ -154,6 +278,27 @@ impl Telemetry {
         format!("{}.{}.{}", major, minor, patch)
     }
 
+    /// Return the firmware bundle version in MAJOR.MINOR.PATCH.BUILD format.
+    pub fn fw_bundle_version_str(&self) -> String {
+        let major = (self.fw_bundle_version >> 24) & 0xFF;
+        let minor = (self.fw_bundle_version >> 16) & 0xFF;
+        let patch = (self.fw_bundle_version >> 8) & 0xFF;
+        let build = self.fw_bundle_version & 0xFF;
+        format!("{}.{}.{}.{}", major, minor, patch, build)
+    }
+
+    /// Return whether the firmware bundle has the feature `flag` (see `FW_FEATURES`),
+    /// or None if `flag` is not a known feature.
+    /// Firmware that does not report a bundle version is treated as having none of the features.
+    pub fn fw_feature(&self, flag: &str) -> Option<bool> {
+        FW_FEATURES
+            .iter()
+            .find(|(name, _)| *name == flag)
+            .map(|(_, first_version)| {
+                self.fw_bundle_version != 0 && self.fw_bundle_version >= *first_version
+            })
+    }
+
     /// Return the board serial number as an integer.
     pub fn board_serial_number(&self) -> u64 {
         ((self.board_id_high as u64) << 32) | self.board_id_low as u64
@@ -166,26 +311,12 @@ impl Telemetry {
 
     /// Return the board type or None if unknown
     pub fn try_board_type(&self) -> Option<&'static str> {
-        let serial_num = self.board_serial_number();
-        let output = match (serial_num >> 36) & 0xFFFFF {
-            0x1 => match (serial_num >> 32) & 0xF {
-                0x2 => "E300_R2",
-                0x3 | 0x4 => "E300_R3",
-                _ => return None,
-            },
-            0x3 => "e150",
-            0x7 => "e75",
-            0x8 => "NEBULA_CB",
-            0xA => "e300",
-            0xB => "GALAXY",
-            0x14 => "n300",
-            0x18 => "n150",
-            0x36 => "p100",
-            0x40 => "p150A",
-            _ => return None,
-        };
+        self.board_info().map(|info| info.name)
+    }
 
-        Some(output)
+    /// Return the board information or None if the board type is unknown
+    pub fn board_info(&self) -> Option<BoardInfo> {
+        BoardInfo::from_serial_number(self.board_serial_number())
     }
 
     /// Return the board type of UNSUPPORTED
@@ -198,6 +329,26 @@ impl Telemetry {
         self.aiclk & 0xffff
     }
 
+    /// Return the clock speed of an L2CPU core in MHz, or None if `core` is not one of the four cores.
+    /// Only reported on blackhole.
+    pub fn l2cpu_clk(&self, core: usize) -> Option<u32> {
+        [
+            self.l2cpuclk0,
+            self.l2cpuclk1,
+            self.l2cpuclk2,
+            self.l2cpuclk3,
+        ]
+        .get(core)
+        .copied()
+    }
+
+    /// Return the number of L2CPU cores that are running, a core is counted if it reports a nonzero clock.
+    pub fn l2cpu_enabled_count(&self) -> u32 {
+        (0..4)
+            .filter(|&core| self.l2cpu_clk(core).is_some_and(|clk| clk != 0))
+            .count() as u32
+    }
+
     /// Return the AXI clock speed in MHz.
     pub fn axi_clk(&self) -> u32 {
         self.axiclk
@@ -247,6 +398,119 @@ impl Telemetry {
     pub fn current(&self) -> f64 {
         (self.tdc & 0xffff) as f64
     }
+
+    /// Return the arc heartbeat counter, this value increases while the arc firmware is running.
+    pub fn telemetry_heartbeat(&self) -> u32 {
+        self.timer_heartbeat
+    }
+
+    /// Return true if any throttler is currently limiting the AI clock.
+    pub fn is_throttling(&self) -> bool {
+        self.throttler != 0
+    }
+
+    /// Return the names of the throttlers that are limiting the AI clock, see `THROTTLER_NAMES`.
+    /// Bits without a known throttler are reported as "unknown".
+    pub fn active_throttlers(&self) -> Vec<&'static str> {
+        let mut active: Vec<_> = THROTTLER_NAMES
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| (self.throttler >> bit) & 0x1 == 1)
+            .map(|(_, name)| *name)
+            .collect();
+        if self.throttler >> THROTTLER_NAMES.len() != 0 {
+            active.push("unknown");
+        }
+
+        active
+    }
+
+    /// Return the TDP limit in watts.
+    pub fn power_limit_watts(&self) -> f64 {
+        (self.tdp >> 16) as f64
+    }
+
+    /// Return the temperature limit at which the chip starts to throttle in degrees celsius.
+    pub fn thermal_limit(&self) -> f64 {
+        (self.thm_limits & 0xffff) as f64
+    }
+
+    /// Return the number of gddr channels that are reported in `ddr_status`.
+    pub fn gddr_channel_count(&self) -> usize {
+        match self.arch {
+            Some(Arch::Grayskull | Arch::Wormhole) => 6,
+            Some(Arch::Blackhole) => 8,
+            Some(Arch::Unknown(_)) | None => 0,
+        }
+    }
+
+    /// Return true if gddr channel `channel` has finished training without an error.
+    /// On grayskull and wormhole each channel has a 4 bit training status in `ddr_status`, grayskull reports
+    /// 1 for a trained channel while wormhole uses the `DramChannelStatus` encoding.
+    /// On blackhole each channel has a trained bit followed by an error bit.
+    pub fn gddr_channel_trained(&self, channel: usize) -> bool {
+        if channel >= self.gddr_channel_count() {
+            return false;
+        }
+
+        match self.arch {
+            Some(Arch::Grayskull) => (self.ddr_status >> (channel * 4)) & 0xF == 1,
+            Some(Arch::Wormhole) => matches!(
+                init::status::DramChannelStatus::try_from(
+                    ((self.ddr_status >> (channel * 4)) & 0xF) as u8
+                ),
+                Ok(init::status::DramChannelStatus::TrainingPass)
+            ),
+            Some(Arch::Blackhole) => (self.ddr_status >> (channel * 2)) & 0x3 == 0x1,
+            Some(Arch::Unknown(_)) | None => false,
+        }
+    }
+
+    /// Return the temperature in degrees celsius that each gddr channel was trained at.
+    /// Only wormhole reports training temperatures, one byte per channel, other arches return an empty list.
+    pub fn gddr_temps(&self) -> Vec<u32> {
+        match self.arch {
+            Some(Arch::Wormhole) => (0..self.gddr_channel_count())
+                .map(|channel| {
+                    let temps = if channel < 4 {
+                        self.gddr_train_temp0
+                    } else {
+                        self.gddr_train_temp1
+                    };
+                    (temps >> ((channel % 4) * 8)) & 0xff
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// The arc scratch register that is read to check basic comms with a chip.
+pub fn arc_scratch_register(arch: Arch) -> &'static str {
+    if arch.is_blackhole() {
+        "arc_ss.reset_unit.SCRATCH_0"
+    } else {
+        "ARC_RESET.SCRATCH[0]"
+    }
+}
+
+/// The ARC_MISC_CNTL bit that haults `core`, the core hault bits are 7:4.
+fn arc_core_hault_bit(arch: Arch, core: u8) -> Result<u32, PlatformError> {
+    if !matches!(arch, Arch::Grayskull | Arch::Wormhole) {
+        return Err(PlatformError::WrongChipArchs {
+            actual: arch,
+            expected: vec![Arch::Grayskull, Arch::Wormhole],
+            backtrace: crate::error::BtWrapper::capture(),
+        });
+    }
+    if core > 3 {
+        return Err(PlatformError::Generic(
+            format!("Invalid ARC core {core}, expected a core between 0 and 3"),
+            crate::error::BtWrapper::capture(),
+        ));
+    }
+
+    Ok(1 << (4 + core))
 }
 
 pub enum ChipInitResult {
@@ -289,6 +553,24 @@ pub trait ChipImpl: HlComms + Send + Sync + 'static {
     /// The information is not cached, so should not be called repeatedly.
     fn get_telemetry(&self) -> Result<Telemetry, PlatformError>;
 
+    /// Get telemetry information from the chip, retrying if the arc updated the table while it was being read.
+    /// The table is read until the heartbeat is unchanged between two consecutive reads, at most `retries` extra times.
+    /// Returns the last read along with whether it was found to be consistent.
+    fn get_telemetry_consistent(&self, retries: u32) -> Result<(Telemetry, bool), PlatformError> {
+        let mut telemetry = self.get_telemetry()?;
+        for _ in 0..=retries {
+            let next = self.get_telemetry()?;
+            let consistent = next.telemetry_heartbeat() == telemetry.telemetry_heartbeat();
+            telemetry = next;
+
+            if consistent {
+                return Ok((telemetry, true));
+            }
+        }
+
+        Ok((telemetry, false))
+    }
+
     /// Send an arc_msg to the underlying chip.
     fn arc_msg(&self, msg: ArcMsgOptions) -> Result<ArcMsgOk, PlatformError>;
 
@@ -302,6 +584,168 @@ pub trait ChipImpl: HlComms + Send + Sync + 'static {
     /// Get information about the underlying chip transport.
     /// This is a hack to get the physical id of the chip.
     fn get_device_info(&self) -> Result<Option<DeviceInfo>, PlatformError>;
+
+    /// Get the ethernet coordinate of this chip.
+    /// Will return an error for arches that do not have ethernet coordinates.
+    fn get_local_chip_coord(&self) -> Result<crate::EthAddr, PlatformError> {
+        Err(format!(
+            "{} chips do not have an ethernet coordinate",
+            self.get_arch()
+        ))?
+    }
+
+    /// Check if the chip is responsive without resetting it.
+    /// This performs a single read of the arc scratch register and, if that succeeds, sends a nop
+    /// arc message with a short timeout.
+    /// On blackhole the arc is reached over the noc, so the default noc tlb is reprogrammed.
+    fn health_check(&self) -> ChipHealth {
+        let scratch = arc_scratch_register(self.get_arch());
+        let (arc_if, chip_if) = self.comms_obj();
+        let noc_responsive =
+            matches!(arc_if.axi_sread32(chip_if, scratch), Ok(value) if value != 0xffffffff);
+
+        // If the arc answered at all it is alive, even if it did not recognize the message.
+        let arc_responsive = noc_responsive
+            && match self.arc_msg(ArcMsgOptions {
+                msg: ArcMsg::Typed(TypedArcMsg::Nop),
+                timeout: std::time::Duration::from_millis(100),
+                ..Default::default()
+            }) {
+                Ok(_) => true,
+                Err(PlatformError::ArcMsgError(ArcMsgError::ProtocolError {
+                    source:
+                        ArcMsgProtocolError::MsgNotRecognized(_)
+                        | ArcMsgProtocolError::UnknownErrorCode(_),
+                    ..
+                })) => true,
+                Err(_) => false,
+            };
+
+        // Only pci connected chips have device info.
+        let is_remote = matches!(self.get_device_info(), Ok(None));
+
+        ChipHealth {
+            arc_responsive,
+            noc_responsive,
+            eth_responsive: is_remote.then_some(noc_responsive),
+        }
+    }
+
+    /// Hault ARC core `core` (0-3) by setting its bit in ARC_RESET.ARC_MISC_CNTL[7:4].
+    ///
+    /// DANGER: core 0 runs the ARC firmware, while it is haulted arc messages, telemetry and fan/power
+    /// management all stop and the chip is only protected by its hardware limits.
+    /// Before haulting core 0 the ARC is moved into A3, if that fails the core is not touched;
+    /// stopping it at an arbitrary point could cut off communication with the voltage regulator.
+    /// Only supported on grayskull and wormhole.
+    fn arc_core_hault(&self, core: u8) -> Result<(), PlatformError> {
+        let bit = arc_core_hault_bit(self.get_arch(), core)?;
+
+        if core == 0 {
+            self.arc_msg(ArcMsgOptions {
+                msg: ArcMsg::Typed(TypedArcMsg::SetArcState {
+                    state: crate::ArcState::A3,
+                }),
+                ..Default::default()
+            })
+            .map_err(|err| {
+                PlatformError::Generic(
+                    format!("Not haulting ARC core 0, could not move the ARC into A3: {err}"),
+                    crate::error::BtWrapper::capture(),
+                )
+            })?;
+        }
+
+        let (arc_if, chip_if) = self.comms_obj();
+        let value = arc_if.axi_sread32(chip_if, "ARC_RESET.ARC_MISC_CNTL")?;
+        arc_if.axi_swrite32(chip_if, "ARC_RESET.ARC_MISC_CNTL", value | bit)?;
+
+        Ok(())
+    }
+
+    /// Release ARC core `core` (0-3) from a hault started by `arc_core_hault`.
+    /// Releasing core 0 resumes the ARC firmware where it was stopped, it stays in A3 until it is
+    /// moved out of it with an arc message.
+    /// Only supported on grayskull and wormhole.
+    fn arc_core_release(&self, core: u8) -> Result<(), PlatformError> {
+        let bit = arc_core_hault_bit(self.get_arch(), core)?;
+
+        let (arc_if, chip_if) = self.comms_obj();
+        let value = arc_if.axi_sread32(chip_if, "ARC_RESET.ARC_MISC_CNTL")?;
+        if value & bit == 0 {
+            return Err(PlatformError::Generic(
+                format!("ARC core {core} is not haulted"),
+                crate::error::BtWrapper::capture(),
+            ));
+        }
+        arc_if.axi_swrite32(chip_if, "ARC_RESET.ARC_MISC_CNTL", value & !bit)?;
+
+        Ok(())
+    }
+
+    /// Get the decoded harvesting mask of this chip.
+    /// Will return an error for arches that do not have a known harvesting layout.
+    fn get_harvesting(&self) -> Result<crate::HarvestingMask, PlatformError> {
+        let mask = match self.arc_msg(ArcMsgOptions {
+            msg: ArcMsg::Typed(TypedArcMsg::GetHarvesting),
+            ..Default::default()
+        })? {
+            ArcMsgOk::Ok { arg, .. } => arg,
+            ArcMsgOk::OkNoWait => unreachable!(),
+        };
+
+        Ok(crate::HarvestingMask::new(self.get_arch(), mask)
+            .ok_or_else(|| format!("Harvesting is not supported for {} chips", self.get_arch()))?)
+    }
+
+    /// Read from the noc after checking that `coord` is a valid location on this chip.
+    /// If `harvesting` is given then harvested tensix cores are also rejected.
+    fn noc_read_checked(
+        &self,
+        noc_id: u8,
+        coord: crate::NocCoord,
+        addr: u64,
+        data: &mut [u8],
+        harvesting: Option<&crate::HarvestingMask>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        coord.validate(noc_id, self.get_arch(), harvesting)?;
+        self.noc_read(noc_id, coord.x, coord.y, addr, data)
+    }
+
+    /// Write to the noc after checking that `coord` is a valid location on this chip.
+    /// If `harvesting` is given then harvested tensix cores are also rejected.
+    fn noc_write_checked(
+        &self,
+        noc_id: u8,
+        coord: crate::NocCoord,
+        addr: u64,
+        data: &[u8],
+        harvesting: Option<&crate::HarvestingMask>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        coord.validate(noc_id, self.get_arch(), harvesting)?;
+        self.noc_write(noc_id, coord.x, coord.y, addr, data)
+    }
+
+    /// Read the noc node id register of the core at (x, y) and return the (x, y) that it reports.
+    /// This can be used to check that an access is actually reaching the expected core,
+    /// the register is read from the noc register block used by tensix and ethernet cores.
+    fn read_noc_node_id(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+    ) -> Result<(u8, u8), Box<dyn std::error::Error>> {
+        let node_id_offset = match self.get_arch() {
+            Arch::Grayskull | Arch::Wormhole => 0x2C,
+            Arch::Blackhole => 0x44,
+            arch @ Arch::Unknown(_) => Err(format!("Cannot read the noc node id of {arch} chips"))?,
+        };
+        let addr = NOC_REGS_BASE + NOC_REGS_STRIDE * noc_id as u64 + node_id_offset;
+
+        let node_id = self.noc_read32(noc_id, x, y, addr)?;
+
+        Ok(((node_id & 0x3f) as u8, ((node_id >> 6) & 0x3f) as u8))
+    }
 }
 
 /// A wrapper around a chip that implements `ChipImpl`.
@@ -310,11 +754,16 @@ pub trait ChipImpl: HlComms + Send + Sync + 'static {
 
 pub struct Chip {
     pub inner: Box<dyn ChipImpl>,
+    /// The status reported by the last wait_for_init run during chip detection, if there was one.
+    pub init_status: Option<InitStatus>,
 }
 
 impl From<Box<dyn ChipImpl>> for Chip {
     fn from(inner: Box<dyn ChipImpl>) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            init_status: None,
+        }
     }
 }
 
@@ -372,4 +821,100 @@ impl ChipImpl for Chip {
     fn get_device_info(&self) -> Result<Option<DeviceInfo>, PlatformError> {
         self.inner.get_device_info()
     }
+
+    fn get_local_chip_coord(&self) -> Result<crate::EthAddr, PlatformError> {
+        self.inner.get_local_chip_coord()
+    }
+
+    fn get_harvesting(&self) -> Result<crate::HarvestingMask, PlatformError> {
+        self.inner.get_harvesting()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Telemetry;
+    use luwen_core::Arch;
+
+    #[test]
+    fn decode_throttlers() {
+        let cases: &[(u32, &[&str])] = &[
+            (0, &[]),
+            (0x1, &["tdp"]),
+            (0x8, &["thermal"]),
+            (
+                0x3f,
+                &[
+                    "tdp",
+                    "fast_tdc",
+                    "tdc",
+                    "thermal",
+                    "board_power",
+                    "gddr_thermal",
+                ],
+            ),
+            (0x22, &["fast_tdc", "gddr_thermal"]),
+            (0x40, &["unknown"]),
+            (0x8000_0004, &["tdc", "unknown"]),
+        ];
+
+        for (throttler, expected) in cases {
+            let telemetry = Telemetry {
+                throttler: *throttler,
+                ..Default::default()
+            };
+            assert_eq!(telemetry.is_throttling(), *throttler != 0, "{throttler:#x}");
+            assert_eq!(telemetry.active_throttlers(), *expected, "{throttler:#x}");
+        }
+    }
+
+    #[test]
+    fn decode_gddr_training() {
+        // (arch, ddr_status, trained channels)
+        let cases: &[(Arch, u32, &[usize])] = &[
+            (Arch::Grayskull, 0, &[]),
+            (Arch::Grayskull, 0x111111, &[0, 1, 2, 3, 4, 5]),
+            (Arch::Grayskull, 0x100210, &[1, 5]),
+            (Arch::Wormhole, 0x222222, &[0, 1, 2, 3, 4, 5]),
+            (Arch::Wormhole, 0x111111, &[]),
+            (Arch::Wormhole, 0x020102, &[0, 4]),
+            (Arch::Blackhole, 0x5555, &[0, 1, 2, 3, 4, 5, 6, 7]),
+            // A channel with its error bit set is not trained.
+            (Arch::Blackhole, 0x5d, &[0, 2, 3]),
+            (Arch::Blackhole, 0, &[]),
+        ];
+
+        for (arch, ddr_status, trained) in cases {
+            let telemetry = Telemetry {
+                arch: Some(*arch),
+                ddr_status: *ddr_status,
+                ..Default::default()
+            };
+            let decoded: Vec<_> = (0..telemetry.gddr_channel_count())
+                .filter(|channel| telemetry.gddr_channel_trained(*channel))
+                .collect();
+            assert_eq!(decoded, *trained, "{arch} {ddr_status:#x}");
+            assert!(!telemetry.gddr_channel_trained(telemetry.gddr_channel_count()));
+        }
+    }
+
+    #[test]
+    fn decode_limits() {
+        // (tdp, thm_limits, power limit, thermal limit)
+        let cases = [
+            (0, 0, 0.0, 0.0),
+            (0x00960032, 0x0000004b, 150.0, 75.0),
+            (0xffff0000, 0x005a0055, 65535.0, 85.0),
+        ];
+
+        for (tdp, thm_limits, power_limit, thermal_limit) in cases {
+            let telemetry = Telemetry {
+                tdp,
+                thm_limits,
+                ..Default::default()
+            };
+            assert_eq!(telemetry.power_limit_watts(), power_limit, "{tdp:#x}");
+            assert_eq!(telemetry.thermal_limit(), thermal_limit, "{thm_limits:#x}");
+        }
+    }
 }
@@ -4,10 +4,11 @@
 use super::{eth_addr::EthAddr, HlComms, MemorySlices, Wormhole};
 use crate::{
     chip::communication::{
-        chip_comms::{axi_translate, AxiData, AxiError, ChipComms},
+        chip_comms::{axi_symbols, axi_translate, AxiData, AxiError, ChipComms},
         chip_interface::ChipInterface,
     },
     error::PlatformError,
+    BroadcastConfig,
 };
 
 pub struct RemoteArcIf {
@@ -20,6 +21,10 @@ impl ChipComms for RemoteArcIf {
         axi_translate(self.axi_data.as_ref(), addr)
     }
 
+    fn axi_symbols(&self) -> Result<Vec<(String, u64, u64)>, AxiError> {
+        axi_symbols(self.axi_data.as_ref())
+    }
+
     fn axi_read(
         &self,
         chip_if: &dyn ChipInterface,
@@ -68,8 +73,32 @@ impl ChipComms for RemoteArcIf {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        chip_if.eth_noc_broadcast(self.addr, noc_id, addr, data)
+        chip_if.eth_noc_broadcast(self.addr, noc_id, addr, data, config)
+    }
+
+    fn axi_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        chip_if.eth_noc_rmw32(self.addr, 0, 0, 10, addr, mask, value)
+    }
+
+    fn noc_rmw32(
+        &self,
+        chip_if: &dyn ChipInterface,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        chip_if.eth_noc_rmw32(self.addr, noc_id, x, y, addr, mask, value)
     }
 }
 
@@ -156,17 +185,6 @@ impl EthAddresses {
 }
 
 impl Wormhole {
-    pub fn get_local_chip_coord(&self) -> Result<EthAddr, PlatformError> {
-        let coord = self.noc_read32(0, 9, 0, self.eth_addrs.node_info + 8)?;
-
-        Ok(EthAddr {
-            rack_x: (coord & 0xFF) as u8,
-            rack_y: ((coord >> 8) & 0xFF) as u8,
-            shelf_x: ((coord >> 16) & 0xFF) as u8,
-            shelf_y: ((coord >> 24) & 0xFF) as u8,
-        })
-    }
-
     pub(crate) fn check_ethernet_training_complete(&mut self) -> Result<Vec<bool>, PlatformError> {
         self.init_eth_addrs()?;
 
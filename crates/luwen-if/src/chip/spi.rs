@@ -821,3 +821,35 @@ impl ActiveSpi {
         Ok(())
     }
 }
+
+/// Update a running crc32 (IEEE) with `data`.
+/// Start from 0xffffffff and invert the value after the last update to get the checksum.
+pub fn crc32_update(mut crc: u32, data: &[u8]) -> u32 {
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    crc
+}
+
+#[cfg(test)]
+mod test {
+    use super::crc32_update;
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(!crc32_update(0xffffffff, b"123456789"), 0xcbf43926);
+        assert_eq!(!crc32_update(0xffffffff, b""), 0);
+
+        // Updating in chunks gives the same result as a single update.
+        let crc = crc32_update(crc32_update(0xffffffff, b"1234"), b"56789");
+        assert_eq!(!crc, 0xcbf43926);
+    }
+}
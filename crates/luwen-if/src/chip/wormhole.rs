@@ -210,6 +210,76 @@ impl Wormhole {
         Self::init(true, true, arc_if, self.chip_if.clone())
     }
 
+    /// Open the remote chip at the absolute ethernet address `addr`.
+    /// Unlike open_remote, this checks that the chip answering at `addr` reports that coordinate,
+    /// so an address that is not reachable from this chip is returned as an error.
+    pub fn open_remote_abs(&self, addr: EthAddr) -> Result<Wormhole, PlatformError> {
+        let remote = self.open_remote(addr)?;
+
+        let coord = remote.get_local_chip_coord().map_err(|err| {
+            PlatformError::Generic(
+                format!("Could not reach chip {addr} from this chip: {err}"),
+                BtWrapper::capture(),
+            )
+        })?;
+        if coord != addr {
+            return Err(PlatformError::Generic(
+                format!("Chip {addr} is not reachable from this chip, the chip at that address reported {coord}"),
+                BtWrapper::capture(),
+            ));
+        }
+
+        Ok(remote)
+    }
+
+    /// Check that the chip at `addr` answers over ethernet before committing to open_remote.
+    /// This is a single ethernet read of the noc node id of one of the remote ethernet cores,
+    /// so an unreachable chip fails after one ethernet timeout rather than partway through init.
+    /// If `timeout` is set it replaces the ethernet timeout of the transport for this read only.
+    pub fn probe_remote(
+        &self,
+        addr: impl IntoChip<EthAddr>,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<(), PlatformError> {
+        let addr = addr.cinto(&self.arc_if, &self.chip_if)?;
+
+        // The same ethernet core that get_local_chip_coord reads the chip coordinate from.
+        let (x, y) = (9, 0);
+        let mut node_id = [0; 4];
+        let node_id_addr = super::NOC_REGS_BASE + 0x2C;
+        match timeout {
+            Some(timeout) => self.chip_if.eth_noc_read_timeout(
+                addr,
+                0,
+                x,
+                y,
+                node_id_addr,
+                &mut node_id,
+                timeout,
+            ),
+            None => self
+                .chip_if
+                .eth_noc_read(addr, 0, x, y, node_id_addr, &mut node_id),
+        }
+        .map_err(|err| {
+            PlatformError::Generic(
+                format!("Chip {addr} did not respond over ethernet: {err}"),
+                BtWrapper::capture(),
+            )
+        })?;
+
+        let node_id = u32::from_le_bytes(node_id);
+        let node = ((node_id & 0x3f) as u8, ((node_id >> 6) & 0x3f) as u8);
+        if node != (x, y) {
+            return Err(PlatformError::Generic(
+                format!("Chip {addr} did not respond correctly over ethernet, expected node id {x}-{y} but read {node_id:#x}"),
+                BtWrapper::capture(),
+            ));
+        }
+
+        Ok(())
+    }
+
     // fn check_dram_trained(&self) {
     //     let pc = self.axi_sread32("ARC_RESET.POST_CODE")?;
 
@@ -314,6 +384,14 @@ impl Wormhole {
         Ok(())
     }
 
+    /// Check whether noc coordinate translation is enabled, this only reads the niu_cfg of a dram core
+    /// rather than the full telemetry table.
+    pub fn noc_translation_enabled(&self) -> Result<bool, PlatformError> {
+        // Magic value referring to the location of the niu_cfg for a DRAM
+        let niu_cfg = self.noc_read32(0, 0, 0, 0x1000A0000 + 0x100)?;
+        Ok((niu_cfg & (1 << 14)) != 0)
+    }
+
     pub fn spi_write(&self, addr: u32, value: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
         let spi = super::spi::ActiveSpi::new(self, self.use_arc_for_spi)?;
 
@@ -829,23 +907,25 @@ impl ChipImpl for Wormhole {
     }
 
     fn arc_msg(&self, msg: ArcMsgOptions) -> Result<ArcMsgOk, PlatformError> {
-        let (msg_reg, return_reg) = if msg.use_second_mailbox {
-            (2, 4)
-        } else {
-            (5, 3)
-        };
-
-        self.check_arc_msg_safe(msg_reg, return_reg)?;
-
-        crate::arc_msg::arc_msg(
-            self,
-            &msg.msg,
-            msg.wait_for_done,
-            msg.timeout,
-            msg_reg,
-            return_reg,
-            msg.addrs.as_ref().unwrap_or(&self.arc_addrs),
-        )
+        super::arc_msg_with_retries(&msg, |msg| {
+            let (msg_reg, return_reg) = if msg.use_second_mailbox {
+                (2, 4)
+            } else {
+                (5, 3)
+            };
+
+            self.check_arc_msg_safe(msg_reg, return_reg)?;
+
+            crate::arc_msg::arc_msg(
+                self,
+                &msg.msg,
+                msg.wait_for_done,
+                msg.timeout,
+                msg_reg,
+                return_reg,
+                msg.addrs.as_ref().unwrap_or(&self.arc_addrs),
+            )
+        })
     }
 
     fn get_neighbouring_chips(&self) -> Result<Vec<NeighbouringChip>, crate::error::PlatformError> {
@@ -1112,6 +1192,7 @@ impl ChipImpl for Wormhole {
         }
 
         Ok(super::Telemetry {
+            arch: Some(luwen_core::Arch::Wormhole),
             board_id: ((board_id_high as u64) << 32) | (board_id_low as u64),
             enum_version,
             device_id,
@@ -1161,6 +1242,8 @@ impl ChipImpl for Wormhole {
             eth_debug_status1,
             tt_flash_version,
             fw_bundle_version,
+            // On wh/gs the arc0 health counter is the arc heartbeat.
+            timer_heartbeat: arc0_health,
             ..Default::default()
         })
     }
@@ -1172,4 +1255,15 @@ impl ChipImpl for Wormhole {
             Ok(self.chip_if.get_device_info()?)
         }
     }
+
+    fn get_local_chip_coord(&self) -> Result<EthAddr, PlatformError> {
+        let coord = self.noc_read32(0, 9, 0, self.eth_addrs.node_info + 8)?;
+
+        Ok(EthAddr {
+            rack_x: (coord & 0xFF) as u8,
+            rack_y: ((coord >> 8) & 0xFF) as u8,
+            shelf_x: ((coord >> 16) & 0xFF) as u8,
+            shelf_y: ((coord >> 24) & 0xFF) as u8,
+        })
+    }
 }
@@ -6,11 +6,17 @@ use std::collections::HashSet;
 use luwen_core::Arch;
 
 use crate::{
-    chip::{wait_for_init, Chip, InitError, InitStatus},
+    chip::{
+        wait_for_init, Blackhole, CallReason, Chip, ChipDetectState, FailureTolerance, Grayskull,
+        InitError, InitStatus, Wormhole,
+    },
     error::{BtWrapper, PlatformError},
     ChipImpl, EthAddr,
 };
 
+/// How long wait_for_arc_alive sleeps between checks of the arc status.
+const ARC_ALIVE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
 #[derive(PartialEq, Eq, Hash, Debug, Clone)]
 enum InterfaceIdOrCoord {
     Id(u32),
@@ -32,18 +38,30 @@ pub enum UninitChip {
 }
 
 // HACK(drosen): Probably should just implement clone on Chip...
-fn clone_chip(chip: &Chip) -> Chip {
-    if let Some(wh) = chip.as_wh() {
+fn clone_chip(chip: &dyn ChipImpl) -> Chip {
+    let chip = chip.as_any();
+    if let Some(wh) = chip.downcast_ref::<Wormhole>() {
         Chip::from(Box::new(wh.clone()) as Box<dyn ChipImpl>)
-    } else if let Some(gs) = chip.as_gs() {
+    } else if let Some(gs) = chip.downcast_ref::<Grayskull>() {
         Chip::from(Box::new(gs.clone()) as Box<dyn ChipImpl>)
-    } else if let Some(bh) = chip.as_bh() {
+    } else if let Some(bh) = chip.downcast_ref::<Blackhole>() {
         Chip::from(Box::new(bh.clone()) as Box<dyn ChipImpl>)
     } else {
-        unimplemented!(
-            "Don't have a clone handler for chip with arch {:?}.",
-            chip.get_arch()
-        )
+        unimplemented!("Don't have a clone handler for chip {:?}.", chip.type_id())
+    }
+}
+
+/// Same as clone_chip, but also keeps the init status recorded on the chip.
+fn clone_chip_with_status(chip: &Chip) -> Chip {
+    let mut output = clone_chip(chip);
+    output.init_status = chip.init_status.clone();
+    output
+}
+
+impl Clone for Chip {
+    /// The clone refers to the same device, comms are shared between the two.
+    fn clone(&self) -> Self {
+        clone_chip_with_status(self)
     }
 }
 
@@ -52,16 +70,17 @@ impl Clone for UninitChip {
         match self {
             Self::Partially { status, underlying } => Self::Partially {
                 status: status.clone(),
-                underlying: clone_chip(underlying),
+                underlying: clone_chip_with_status(underlying),
             },
-            Self::Initialized(chip) => Self::Initialized(clone_chip(chip)),
+            Self::Initialized(chip) => Self::Initialized(clone_chip_with_status(chip)),
         }
     }
 }
 
 impl UninitChip {
     pub fn new(status: InitStatus, chip: &Chip) -> Self {
-        let chip = clone_chip(chip);
+        let mut chip = clone_chip(chip);
+        chip.init_status = Some(status.clone());
         if status.init_complete() && !status.has_error() {
             UninitChip::Initialized(chip)
         } else {
@@ -72,6 +91,17 @@ impl UninitChip {
         }
     }
 
+    /// Take a copy of a chip that is in the middle of being initialized.
+    /// Returns None if initialization has not yet reported a status for the chip.
+    pub fn from_detect_state(state: &ChipDetectState) -> Option<Self> {
+        match state.call {
+            CallReason::InitWait(status) | CallReason::ChipInitCompleted(status) => {
+                Some(UninitChip::new(status.clone(), &clone_chip(state.chip)))
+            }
+            CallReason::NewChip | CallReason::NotNew => None,
+        }
+    }
+
     pub fn status(&self) -> Option<&InitStatus> {
         match self {
             UninitChip::Partially { status, .. } => Some(status),
@@ -87,7 +117,14 @@ impl UninitChip {
     ) -> Result<Chip, InitError<E>> {
         match self {
             UninitChip::Partially { mut underlying, .. } => {
-                wait_for_init(&mut underlying, init_callback, false, false)?;
+                let status = wait_for_init(
+                    &mut underlying,
+                    init_callback,
+                    false,
+                    false,
+                    FailureTolerance::default(),
+                )?;
+                underlying.init_status = Some(status);
 
                 Ok(underlying)
             }
@@ -170,6 +207,54 @@ impl UninitChip {
             UninitChip::Initialized(_) => true,
         }
     }
+
+    /// Recheck the chip status until the arc is alive or `timeout` has elapsed, returns the final value of `arc_alive`.
+    /// The status is checked from scratch so this can be used to wait for the arc to come back after a reset.
+    /// `callback` is called between checks, returning an error from it will abort the wait.
+    pub fn wait_for_arc_alive<E>(
+        &mut self,
+        timeout: std::time::Duration,
+        callback: &mut impl FnMut() -> Result<(), E>,
+    ) -> Result<bool, InitError<E>> {
+        let (status, underlying) = match self {
+            UninitChip::Partially { status, underlying } => (status, underlying),
+            UninitChip::Initialized(_) => return Ok(true),
+        };
+
+        let new_status = || {
+            let mut new_status = InitStatus::new_unknown();
+            new_status.init_options = status.init_options.clone();
+            new_status
+        };
+
+        let start = std::time::Instant::now();
+        let mut current = new_status();
+        loop {
+            // While the chip is coming out of reset the checks may fail outright,
+            // so treat an error the same as the arc not being ready yet.
+            let result = underlying.update_init_state(&mut current);
+            if result.is_err()
+                || (!current.arc_status.is_waiting() && current.arc_status.has_error())
+            {
+                current = new_status();
+            } else if !current.arc_status.is_waiting() {
+                break;
+            }
+
+            let Some(remaining) = timeout.checked_sub(start.elapsed()) else {
+                break;
+            };
+
+            callback().map_err(InitError::CallbackError)?;
+
+            // Each check is a full status update, give the arc some time to boot between them.
+            std::thread::sleep(remaining.min(ARC_ALIVE_POLL_INTERVAL));
+        }
+
+        **status = current;
+
+        Ok(self.arc_alive())
+    }
 }
 
 pub struct ChipDetectOptions {
@@ -183,6 +268,13 @@ pub struct ChipDetectOptions {
     pub chip_filter: Vec<Arch>,
     /// If true, then we will not initialize anything that might cause a problem (i.e. a noc hang).
     pub noc_safe: bool,
+    /// If false, a chip whose ARC failed to initialize (or that we lost communication with) is raised as an Err(..)
+    /// even when continue_on_failure is true.
+    pub tolerate_arc_failure: bool,
+    /// If false, a chip whose DRAM failed to train is raised as an Err(..).
+    pub tolerate_dram_failure: bool,
+    /// If false, a chip whose ethernet failed to initialize is raised as an Err(..).
+    pub tolerate_eth_failure: bool,
 }
 
 impl Default for ChipDetectOptions {
@@ -192,6 +284,9 @@ impl Default for ChipDetectOptions {
             local_only: false,
             chip_filter: Vec::new(),
             noc_safe: false,
+            tolerate_arc_failure: true,
+            tolerate_dram_failure: true,
+            tolerate_eth_failure: true,
         }
     }
 }
@@ -215,6 +310,21 @@ impl ChipDetectOptions {
         self.noc_safe = noc_safe;
         self
     }
+
+    pub fn tolerate_arc_failure(mut self, tolerate: bool) -> Self {
+        self.tolerate_arc_failure = tolerate;
+        self
+    }
+
+    pub fn tolerate_dram_failure(mut self, tolerate: bool) -> Self {
+        self.tolerate_dram_failure = tolerate;
+        self
+    }
+
+    pub fn tolerate_eth_failure(mut self, tolerate: bool) -> Self {
+        self.tolerate_eth_failure = tolerate;
+        self
+    }
 }
 
 /// Find all chips accessible from the given set of root chips.
@@ -249,21 +359,47 @@ impl ChipDetectOptions {
 ///     a. This is catastrophic, we cannot continue searching for chips, because some of the chips in the mesh may no longer be accesible
 ///     b. We could recover from this by rerunning the search, but this is not implemented.
 pub fn detect_chips<E>(
-    mut root_chips: Vec<Chip>,
+    root_chips: Vec<Chip>,
     init_callback: &mut impl FnMut(crate::chip::ChipDetectState) -> Result<(), E>,
     options: ChipDetectOptions,
 ) -> Result<Vec<UninitChip>, InitError<E>> {
+    let mut output = Vec::new();
+    detect_chips_streaming(root_chips, init_callback, options, &mut |chip| {
+        output.push(chip);
+        Ok(())
+    })?;
+
+    Ok(output)
+}
+
+/// Performs the same search as `detect_chips`, but hands each chip to `chip_found` as soon as it
+/// has been initialized instead of collecting them into a list.
+/// An error returned from `chip_found` will stop the search.
+pub fn detect_chips_streaming<E>(
+    mut root_chips: Vec<Chip>,
+    init_callback: &mut impl FnMut(crate::chip::ChipDetectState) -> Result<(), E>,
+    options: ChipDetectOptions,
+    chip_found: &mut impl FnMut(UninitChip) -> Result<(), E>,
+) -> Result<(), InitError<E>> {
     let ChipDetectOptions {
         continue_on_failure,
         local_only,
         chip_filter,
         noc_safe,
+        tolerate_arc_failure,
+        tolerate_dram_failure,
+        tolerate_eth_failure,
     } = options;
 
+    let tolerance = FailureTolerance {
+        arc: tolerate_arc_failure,
+        dram: tolerate_dram_failure,
+        eth: tolerate_eth_failure,
+    };
+
     let mut remotes_to_investigate = Vec::new();
     let mut seen_chips = HashSet::new();
 
-    let mut output = Vec::new();
     for (root_index, root_chip) in root_chips.iter_mut().enumerate() {
         if !chip_filter.is_empty() && !chip_filter.contains(&root_chip.get_arch()) {
             Err(PlatformError::WrongChipArchs {
@@ -273,7 +409,13 @@ pub fn detect_chips<E>(
             })?;
         }
 
-        let status = wait_for_init(root_chip, init_callback, continue_on_failure, noc_safe)?;
+        let status = wait_for_init(
+            root_chip,
+            init_callback,
+            continue_on_failure,
+            noc_safe,
+            tolerance,
+        )?;
 
         // We now want to convert to the uninitialized chip type.
         let chip = UninitChip::new(status, root_chip);
@@ -283,7 +425,7 @@ pub fn detect_chips<E>(
         let remote_ready = chip.eth_safe();
         let arc_ready = chip.arc_alive();
 
-        output.push(chip);
+        chip_found(chip).map_err(InitError::CallbackError)?;
 
         let ident = if let Some(wh) = root_chip.as_wh() {
             if arc_ready {
@@ -335,7 +477,13 @@ pub fn detect_chips<E>(
             if let Some(wh) = root_chip.as_wh() {
                 let mut wh = wh.open_remote(nchip.eth_addr)?;
 
-                let status = wait_for_init(&mut wh, init_callback, continue_on_failure, noc_safe)?;
+                let status = wait_for_init(
+                    &mut wh,
+                    init_callback,
+                    continue_on_failure,
+                    noc_safe,
+                    tolerance,
+                )?;
 
                 let local_coord = wh.get_local_chip_coord()?;
 
@@ -371,14 +519,14 @@ pub fn detect_chips<E>(
                 }
 
                 let chip = Chip::from(Box::new(wh) as Box<dyn ChipImpl>);
-                output.push(UninitChip::new(status, &chip));
+                chip_found(UninitChip::new(status, &chip)).map_err(InitError::CallbackError)?;
             } else {
                 unimplemented!("Don't have a handler for non-WH chips with ethernet support yet.")
             }
         }
     }
 
-    Ok(output)
+    Ok(())
 }
 
 pub fn detect_initialized_chips<E>(
@@ -47,6 +47,29 @@ pub enum ArcReadyError {
     OldPostCode(u32),
 }
 
+/// Errors a backend can report from a noc read or write so that callers can tell
+/// the failure modes apart without parsing messages.
+#[derive(Error, Debug)]
+pub enum NocError {
+    #[error("{0}")]
+    Timeout(String),
+
+    #[error("Tile ({x}, {y}) on noc {noc_id} is outside of the noc grid")]
+    InvalidCoord { noc_id: u8, x: u32, y: u32 },
+
+    #[error("Tile ({x}, {y}) on noc {noc_id} is a harvested tensix core")]
+    HarvestedCoord { noc_id: u8, x: u8, y: u8 },
+
+    #[error("TLB {index} is not available")]
+    TlbUnavailable { index: u32 },
+
+    #[error("{0}")]
+    HardwareFault(String),
+
+    #[error("{0}")]
+    Unsupported(String),
+}
+
 #[derive(Error, Debug)]
 pub enum PlatformError {
     #[error("Tried to initialize chip with the wrong architecture, expected {expected:?} but got {actual:?}\n{backtrace}")]
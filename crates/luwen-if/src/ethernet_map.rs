@@ -0,0 +1,284 @@
+// SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+
+use luwen_core::Arch;
+
+use crate::{
+    chip::{ArcMsgOptions, Chip, NeighbouringChip},
+    error::{NocError, PlatformError},
+    ArcMsg, ArcMsgOk, ChipImpl, EthAddr, HarvestingMask, NocCoord, TypedArcMsg,
+};
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct ChipIdent {
+    arch: Arch,
+    board_id: Option<u64>,
+    interface: Option<u32>,
+    coord: Option<EthAddr>,
+}
+
+/// A chip in the ethernet map, the id of a chip is its index in `EthernetMap::chips`.
+#[derive(Debug, Clone)]
+pub struct EthernetMapChip {
+    pub arch: Arch,
+    pub board_id: Option<u64>,
+    pub coord: Option<EthAddr>,
+    /// The pci interface of the chip, if it is directly connected to the host.
+    pub mmio_interface: Option<u32>,
+    pub noc_translation_en: bool,
+    pub harvest_mask: u32,
+    pub boardtype: Option<String>,
+}
+
+/// An ethernet link between two (chip id, ethernet channel) pairs.
+pub type EthernetConnection = ((usize, usize), (usize, usize));
+
+/// The chips in a system and the ethernet links between them.
+#[derive(Debug, Clone)]
+pub struct EthernetMap {
+    pub chips: Vec<EthernetMapChip>,
+    /// Every link is listed once, with the lower (chip, channel) pair first.
+    pub connections: Vec<EthernetConnection>,
+}
+
+impl EthernetMap {
+    /// Build the ethernet map from a list of detected chips.
+    /// Chips that are found more than once (i.e. through both pci and ethernet) are only listed once.
+    pub fn build(detected_chips: &[Chip]) -> Result<Self, PlatformError> {
+        let mut chips = HashMap::new();
+        let mut map_chips: Vec<EthernetMapChip> = Vec::new();
+        let mut connection_map = HashMap::new();
+
+        for chip in detected_chips {
+            let telemetry = chip.get_telemetry()?;
+
+            let (ident, data, mmio_interface) = if let Some(wh) = chip.as_wh() {
+                let coord = wh.get_local_chip_coord()?;
+
+                let noc_translation_en = wh.noc_translation_enabled()?;
+
+                let harvest_mask = get_harvest_mask(wh)?;
+
+                let ident = ChipIdent {
+                    arch: Arch::Wormhole,
+                    board_id: Some(telemetry.board_id),
+                    interface: None,
+                    coord: Some(coord),
+                };
+
+                let data = EthernetMapChip {
+                    arch: Arch::Wormhole,
+                    board_id: Some(telemetry.board_id),
+                    coord: Some(coord),
+                    mmio_interface: None,
+                    noc_translation_en,
+                    harvest_mask,
+                    boardtype: telemetry.try_board_type().map(|v| v.to_string()),
+                };
+
+                let mmio_interface = if !wh.is_remote {
+                    wh.get_device_info()?.map(|v| v.interface_id)
+                } else {
+                    None
+                };
+
+                let neighbours = wh.get_neighbouring_chips()?;
+
+                let mut connection_info: HashMap<_, Vec<_>> = HashMap::new();
+                for NeighbouringChip {
+                    local_noc_addr,
+                    remote_noc_addr,
+                    eth_addr,
+                } in neighbours
+                {
+                    let next = wh.open_remote(eth_addr)?;
+
+                    let next_ident = ChipIdent {
+                        arch: Arch::Wormhole,
+                        board_id: Some(next.get_telemetry()?.board_id),
+                        interface: None,
+                        coord: Some(eth_addr),
+                    };
+
+                    let local_id = wh
+                        .eth_locations
+                        .iter()
+                        .position(|v| (v.x, v.y) == local_noc_addr)
+                        .unwrap();
+
+                    let remote_id = next
+                        .eth_locations
+                        .iter()
+                        .position(|v| (v.x, v.y) == remote_noc_addr)
+                        .unwrap();
+
+                    connection_info
+                        .entry(next_ident)
+                        .or_default()
+                        .push((local_id, remote_id));
+                }
+                connection_map.insert(ident.clone(), connection_info);
+
+                (ident, data, mmio_interface)
+            } else if let Some(gs) = chip.as_gs() {
+                let harvest_mask = get_harvest_mask(gs)?;
+                let interface = gs.get_device_info()?.map(|v| v.interface_id);
+
+                let ident = ChipIdent {
+                    arch: Arch::Grayskull,
+                    board_id: None,
+                    interface,
+                    coord: None,
+                };
+
+                let data = EthernetMapChip {
+                    arch: Arch::Grayskull,
+                    board_id: None,
+                    coord: None,
+                    mmio_interface: None,
+                    noc_translation_en: false,
+                    harvest_mask,
+                    boardtype: telemetry.try_board_type().map(|v| v.to_string()),
+                };
+
+                (ident, data, interface)
+            } else if let Some(bh) = chip.as_bh() {
+                let interface = bh.get_device_info()?.map(|v| v.interface_id);
+
+                let ident = ChipIdent {
+                    arch: Arch::Blackhole,
+                    board_id: None,
+                    interface,
+                    coord: None,
+                };
+
+                let data = EthernetMapChip {
+                    arch: Arch::Blackhole,
+                    board_id: None,
+                    coord: None,
+                    mmio_interface: None,
+                    noc_translation_en: false,
+                    harvest_mask: 0,
+                    boardtype: telemetry.try_board_type().map(|v| v.to_string()),
+                };
+
+                (ident, data, interface)
+            } else {
+                unimplemented!("Unknown chip type")
+            };
+
+            let id = *chips.entry(ident).or_insert_with(|| {
+                map_chips.push(data);
+                map_chips.len() - 1
+            });
+            if mmio_interface.is_some() {
+                map_chips[id].mmio_interface = mmio_interface;
+            }
+        }
+
+        let mut ident_order: Vec<_> = chips.iter().collect();
+        ident_order.sort_by_key(|v| v.1);
+
+        let mut connections = Vec::new();
+        let mut known_connections = HashSet::new();
+        for (chip, _) in ident_order {
+            if let Some(connection_info) = connection_map.get(chip) {
+                for (remote_chip, connection) in connection_info {
+                    for (current_eth_id, next_eth_id) in connection {
+                        let local = (chips[chip], *current_eth_id);
+                        let remote = (chips[remote_chip], *next_eth_id);
+
+                        let connection_ident = (local.min(remote), local.max(remote));
+                        if known_connections.insert(connection_ident) {
+                            connections.push(connection_ident);
+                        }
+                    }
+                }
+            }
+        }
+
+        connections.sort();
+
+        Ok(EthernetMap {
+            chips: map_chips,
+            connections,
+        })
+    }
+}
+
+impl EthernetMap {
+    /// Find the id of the chip at `eth_addr` and check that the noc0 coordinate (x, y) is a live core on it.
+    /// Returns None if no chip in the map has that ethernet address.
+    pub fn resolve_coordinate(
+        &self,
+        eth_addr: EthAddr,
+        x: u8,
+        y: u8,
+    ) -> Result<Option<usize>, NocError> {
+        let Some(id) = self.chips.iter().position(|v| v.coord == Some(eth_addr)) else {
+            return Ok(None);
+        };
+
+        let chip = &self.chips[id];
+        let harvesting = HarvestingMask::new(chip.arch, chip.harvest_mask);
+        NocCoord::new(x, y).validate(0, chip.arch, harvesting.as_ref())?;
+
+        Ok(Some(id))
+    }
+}
+
+fn get_harvest_mask(chip: &dyn ChipImpl) -> Result<u32, PlatformError> {
+    let result = chip.arc_msg(ArcMsgOptions {
+        msg: ArcMsg::Typed(TypedArcMsg::GetHarvesting),
+        ..Default::default()
+    })?;
+
+    match result {
+        ArcMsgOk::Ok { arg, .. } => Ok(arg),
+        ArcMsgOk::OkNoWait => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use luwen_core::Arch;
+
+    use super::{EthernetMap, EthernetMapChip};
+    use crate::{error::NocError, EthAddr};
+
+    #[test]
+    fn resolve_coordinate() {
+        let chip = |coord: EthAddr| EthernetMapChip {
+            arch: Arch::Wormhole,
+            board_id: None,
+            coord: Some(coord),
+            mmio_interface: None,
+            noc_translation_en: false,
+            harvest_mask: 0b1,
+            boardtype: None,
+        };
+        let addr = |shelf_x| EthAddr {
+            shelf_x,
+            shelf_y: 0,
+            rack_x: 0,
+            rack_y: 0,
+        };
+        let map = EthernetMap {
+            chips: vec![chip(addr(0)), chip(addr(1))],
+            connections: vec![((0, 0), (1, 0))],
+        };
+
+        assert_eq!(map.resolve_coordinate(addr(1), 1, 1).unwrap(), Some(1));
+        assert_eq!(map.resolve_coordinate(addr(2), 1, 1).unwrap(), None);
+        assert!(matches!(
+            map.resolve_coordinate(addr(0), 10, 12),
+            Err(NocError::InvalidCoord { .. })
+        ));
+        assert!(matches!(
+            map.resolve_coordinate(addr(0), 1, 11),
+            Err(NocError::HarvestedCoord { .. })
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+// SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use luwen_core::Arch;
+
+/// Noc0 y coordinate of the tensix row disabled by each bit of the wormhole harvesting mask.
+const WH_HARVESTING_ROWS: [u8; 10] = [11, 1, 10, 2, 9, 3, 8, 4, 7, 5];
+const WH_TENSIX_COLUMNS: [u8; 8] = [1, 2, 3, 4, 6, 7, 8, 9];
+
+/// Noc0 x coordinate of the tensix column disabled by each bit of the blackhole harvesting mask.
+const BH_HARVESTING_COLUMNS: [u8; 14] = [1, 16, 2, 15, 3, 14, 4, 13, 5, 12, 6, 11, 7, 10];
+const BH_TENSIX_ROWS: [u8; 10] = [2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+
+/// A decoded harvesting mask.
+/// Wormhole harvests tensix rows while blackhole harvests tensix columns,
+/// `rows` and `columns` are indexed by noc0 coordinate and are true if that row/column contains enabled tensix cores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HarvestingMask {
+    pub arch: Arch,
+    /// The raw mask, a set bit means that the row/column was harvested.
+    pub mask: u32,
+    pub grid_size: (u8, u8),
+    pub rows: Vec<bool>,
+    pub columns: Vec<bool>,
+}
+
+impl HarvestingMask {
+    /// Decode the harvesting mask for the given arch.
+    /// Returns None for arches that don't have a known harvesting layout.
+    pub fn new(arch: Arch, mask: u32) -> Option<Self> {
+        // The harvested dimension is listed in the bit order of the harvesting mask.
+        let (tensix_rows, tensix_columns, harvests_rows): (&[u8], &[u8], bool) = match arch {
+            Arch::Wormhole => (&WH_HARVESTING_ROWS, &WH_TENSIX_COLUMNS, true),
+            Arch::Blackhole => (&BH_TENSIX_ROWS, &BH_HARVESTING_COLUMNS, false),
+            Arch::Grayskull | Arch::Unknown(_) => return None,
+        };
+        let grid_size = arch.grid_size()?;
+
+        let decode = |size: u8, tensix: &[u8], harvested: bool| {
+            let mut enabled = vec![false; size as usize];
+            for (bit, &index) in tensix.iter().enumerate() {
+                enabled[index as usize] = !harvested || (mask >> bit) & 0x1 == 0;
+            }
+            enabled
+        };
+
+        Some(Self {
+            arch,
+            mask,
+            grid_size,
+            rows: decode(grid_size.1, tensix_rows, harvests_rows),
+            columns: decode(grid_size.0, tensix_columns, !harvests_rows),
+        })
+    }
+
+    /// The noc0 y coordinates of the rows that contain enabled tensix cores.
+    pub fn enabled_rows(&self) -> Vec<u8> {
+        enabled_indices(&self.rows)
+    }
+
+    /// The noc0 x coordinates of the columns that contain enabled tensix cores.
+    pub fn enabled_columns(&self) -> Vec<u8> {
+        enabled_indices(&self.columns)
+    }
+
+    /// The noc0 y coordinates of the rows that contain harvested tensix cores.
+    pub fn harvested_rows(&self) -> Vec<u8> {
+        match self.arch {
+            Arch::Wormhole => harvested_indices(&WH_HARVESTING_ROWS, self.mask),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The noc0 x coordinates of the columns that contain harvested tensix cores.
+    pub fn harvested_columns(&self) -> Vec<u8> {
+        match self.arch {
+            Arch::Blackhole => harvested_indices(&BH_HARVESTING_COLUMNS, self.mask),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns true if the noc0 coordinate (x, y) is a tensix core that has been harvested.
+    pub fn is_harvested(&self, x: u8, y: u8) -> bool {
+        let harvested = |order: &[u8], index: u8| matches!(order.iter().position(|&v| v == index), Some(bit) if (self.mask >> bit) & 0x1 == 1);
+
+        match self.arch {
+            Arch::Wormhole => harvested(&WH_HARVESTING_ROWS, y) && WH_TENSIX_COLUMNS.contains(&x),
+            Arch::Blackhole => harvested(&BH_HARVESTING_COLUMNS, x) && BH_TENSIX_ROWS.contains(&y),
+            Arch::Grayskull | Arch::Unknown(_) => false,
+        }
+    }
+
+    /// The noc0 (x, y) coordinates of all enabled tensix cores.
+    pub fn enabled_tensix(&self) -> Vec<(u8, u8)> {
+        let rows = self.enabled_rows();
+        self.enabled_columns()
+            .into_iter()
+            .flat_map(|x| rows.iter().map(move |&y| (x, y)))
+            .collect()
+    }
+}
+
+fn harvested_indices(order: &[u8], mask: u32) -> Vec<u8> {
+    let mut harvested: Vec<u8> = order
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| (mask >> bit) & 0x1 == 1)
+        .map(|(_, &index)| index)
+        .collect();
+    harvested.sort();
+    harvested
+}
+
+fn enabled_indices(enabled: &[bool]) -> Vec<u8> {
+    enabled
+        .iter()
+        .enumerate()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(index, _)| index as u8)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use luwen_core::Arch;
+
+    use super::HarvestingMask;
+
+    #[test]
+    fn decode_wormhole() {
+        let harvesting = HarvestingMask::new(Arch::Wormhole, 0b11).unwrap();
+
+        assert_eq!(harvesting.enabled_rows(), vec![2, 3, 4, 5, 7, 8, 9, 10]);
+        assert_eq!(harvesting.enabled_columns(), vec![1, 2, 3, 4, 6, 7, 8, 9]);
+        assert_eq!(harvesting.enabled_tensix().len(), 64);
+        assert!(harvesting.is_harvested(1, 11));
+        assert!(!harvesting.is_harvested(0, 11));
+        assert!(!harvesting.is_harvested(1, 2));
+        assert_eq!(harvesting.harvested_rows(), vec![1, 11]);
+        assert!(harvesting.harvested_columns().is_empty());
+    }
+
+    #[test]
+    fn decode_blackhole() {
+        let harvesting = HarvestingMask::new(Arch::Blackhole, 0b10).unwrap();
+
+        assert_eq!(harvesting.columns.len(), 17);
+        assert!(!harvesting.columns[16]);
+        assert!(harvesting.columns[1]);
+        assert_eq!(harvesting.enabled_columns().len(), 13);
+        assert_eq!(harvesting.enabled_rows().len(), 10);
+
+        assert!(HarvestingMask::new(Arch::Grayskull, 0).is_none());
+    }
+}
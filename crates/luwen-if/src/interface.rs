@@ -1,8 +1,154 @@
 // SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use luwen_core::Arch;
+
 use crate::chip::{eth_addr::EthAddr, ChipInterface};
-use std::fs;
+use crate::error::NocError;
+use crate::{HarvestingMask, NocCoord};
+
+/// Controls which tiles are covered by a noc broadcast.
+/// All coordinates are in the coordinate system of the noc that is used for the broadcast.
+/// The default config covers the same tiles as `BroadcastConfig::default_range`.
+///
+/// Grayskull only supports the default config, a grayskull multicast rectangle does not follow the
+/// wormhole/blackhole coordinate rules so a custom range or excluded rows/columns could write to the
+/// wrong tiles. `check_arch` rejects these configs on grayskull.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BroadcastConfig {
+    /// Override the (x, y) corners of the broadcast rectangle.
+    pub start: Option<(u8, u8)>,
+    pub end: Option<(u8, u8)>,
+    /// Rows and columns that will not be written to.
+    pub exclude_rows: Vec<u8>,
+    pub exclude_columns: Vec<u8>,
+}
+
+impl BroadcastConfig {
+    /// The (start, end) corners of the rectangle covered by a default broadcast on the given arch.
+    pub fn default_range(arch: Arch) -> Option<((u8, u8), (u8, u8))> {
+        let start = match arch {
+            Arch::Grayskull => (0, 0),
+            Arch::Wormhole => (1, 0),
+            Arch::Blackhole => (0, 1),
+            Arch::Unknown(_) => return None,
+        };
+        let (size_x, size_y) = arch.grid_size()?;
+
+        Some((start, (size_x - 1, size_y - 1)))
+    }
+
+    /// Also exclude the rows and columns which contain harvested tensix cores.
+    pub fn exclude_harvested(mut self, noc_id: u8, harvesting: &HarvestingMask) -> Self {
+        let (size_x, size_y) = harvesting.grid_size;
+        // The harvesting mask is described in noc0 coordinates, noc1 runs in the opposite direction.
+        let translate = |index: u8, size: u8| {
+            if noc_id == 0 {
+                index
+            } else {
+                size - 1 - index
+            }
+        };
+
+        self.exclude_rows.extend(
+            harvesting
+                .harvested_rows()
+                .into_iter()
+                .map(|y| translate(y, size_y)),
+        );
+        self.exclude_columns.extend(
+            harvesting
+                .harvested_columns()
+                .into_iter()
+                .map(|x| translate(x, size_x)),
+        );
+
+        self
+    }
+
+    /// Check that this config can be used for a broadcast on the given arch,
+    /// on grayskull only the default full grid broadcast is supported.
+    pub fn check_arch(&self, arch: Arch) -> Result<(), NocError> {
+        if arch.is_grayskull() && *self != Self::default() {
+            return Err(NocError::Unsupported(
+                "Grayskull only supports broadcasting to the full noc grid, a custom range or excluded rows/columns cannot be used".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check that the broadcast range lies on the noc grid of the given arch and that start is not past end.
+    /// If `harvesting` is given then the broadcast is also rejected if it would write to a harvested tensix core
+    /// that has not been excluded, the error names the first such coordinate.
+    pub fn validate(
+        &self,
+        noc_id: u8,
+        arch: Arch,
+        harvesting: Option<&HarvestingMask>,
+    ) -> Result<(), NocError> {
+        self.check_arch(arch)?;
+
+        let Some(default) = Self::default_range(arch) else {
+            return Ok(());
+        };
+        let start = self.start.unwrap_or(default.0);
+        let end = self.end.unwrap_or(default.1);
+
+        NocCoord::new(start.0, start.1).validate(noc_id, arch, None)?;
+        NocCoord::new(end.0, end.1).validate(noc_id, arch, None)?;
+        if start.0 > end.0 || start.1 > end.1 {
+            return Err(NocError::InvalidCoord {
+                noc_id,
+                x: start.0 as u32,
+                y: start.1 as u32,
+            });
+        }
+
+        if let Some(harvesting) = harvesting {
+            for (start, end) in self.rectangles(default) {
+                for x in start.0..=end.0 {
+                    for y in start.1..=end.1 {
+                        NocCoord::new(x, y).validate(noc_id, arch, Some(harvesting))?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Split the broadcast into the list of (start, end) rectangles that skip the excluded rows and columns.
+    /// `default` is the range that is used if `start` or `end` are not set.
+    pub fn rectangles(&self, default: ((u8, u8), (u8, u8))) -> Vec<((u8, u8), (u8, u8))> {
+        let (start_x, start_y) = self.start.unwrap_or(default.0);
+        let (end_x, end_y) = self.end.unwrap_or(default.1);
+
+        // Group the remaining indices into contiguous (start, end) spans.
+        let spans = |start: u8, end: u8, exclude: &[u8]| {
+            let mut spans: Vec<(u8, u8)> = Vec::new();
+            for index in (start..=end).filter(|v| !exclude.contains(v)) {
+                match spans.last_mut() {
+                    Some(span) if span.1 + 1 == index => span.1 = index,
+                    _ => spans.push((index, index)),
+                }
+            }
+            spans
+        };
+
+        let columns = spans(start_x, end_x, &self.exclude_columns);
+        let rows = spans(start_y, end_y, &self.exclude_rows);
+
+        let mut rectangles = Vec::with_capacity(columns.len() * rows.len());
+        for &(x_start, x_end) in &columns {
+            for &(y_start, y_end) in &rows {
+                rectangles.push(((x_start, y_start), (x_end, y_end)));
+            }
+        }
+
+        rectangles
+    }
+}
 
 #[derive(Debug)]
 pub enum FnNoc {
@@ -27,6 +173,19 @@ pub enum FnNoc {
         addr: u64,
         data: *const u8,
         len: u64,
+        config: BroadcastConfig,
+    },
+    /// Replace the bits of the 32 bit register at addr selected by mask with those of value,
+    /// the read and the write must not be interleaved with any other access to the chip.
+    /// The written value is stored in result.
+    Rmw32 {
+        noc_id: u8,
+        x: u32,
+        y: u32,
+        addr: u64,
+        mask: u32,
+        value: u32,
+        result: *mut u32,
     },
 }
 
@@ -34,6 +193,8 @@ pub enum FnNoc {
 pub struct FnRemote {
     pub addr: EthAddr,
     pub rw: FnNoc,
+    /// Replaces the ethernet timeout of the transport for this access.
+    pub timeout: Option<std::time::Duration>,
 }
 
 #[derive(Debug)]
@@ -48,6 +209,13 @@ pub enum FnAxi {
         data: *const u8,
         len: u32,
     },
+    /// Same as FnNoc::Rmw32 but for an axi address.
+    Rmw32 {
+        addr: u32,
+        mask: u32,
+        value: u32,
+        result: *mut u32,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -63,67 +231,35 @@ pub struct DeviceInfo {
     pub device_id: u16,
     pub board_id: u16,
     pub bar_size: u64,
-}
 
-impl DeviceInfo {
-    /// Return the sysfs path for the PCIe device.
-    fn pcie_base_path(&self) -> String {
-        let domain = format!("{:04x}", self.domain);
-        let bus = format!("{:02x}", self.bus);
-        let slot = format!("{:02x}", self.slot);
-        let function = format!("{:01x}", self.function);
-        format!(
-            "/sys/bus/pci/devices/{}:{}:{}.{}/",
-            domain, bus, slot, function
-        )
-    }
-
-    /// Return link width; valid values of `s` are "current" and "max".
-    fn pcie_link_width(&self, s: &str) -> u32 {
-        let base_path = self.pcie_base_path();
-        let path = format!("{}{}{}", &base_path, s, "_link_width");
-        let width = fs::read_to_string(path)
-            .map(|s| s.trim().to_string())
-            .unwrap();
-        width.parse::<u32>().unwrap()
-    }
+    pub pcie_current_link_width: u32,
+    pub pcie_current_link_gen: i32,
+    pub pcie_max_link_width: u32,
+    pub pcie_max_link_gen: i32,
 
-    /// Return link gen; valid values of `s` are "current" and "max".
-    fn pcie_link_gen(&self, s: &str) -> i32 {
-        let base_path = self.pcie_base_path();
-        let path = format!("{}{}{}", &base_path, s, "_link_speed");
-        let speed = fs::read_to_string(path)
-            .map(|s| s.trim().to_string())
-            .unwrap();
-        match speed.split_whitespace().next().unwrap_or("") {
-            "2.5" => 1,
-            "5.0" => 2,
-            "8.0" => 3,
-            "16.0" => 4,
-            "32.0" => 5,
-            "64.0" => 6,
-            _ => -1,
-        }
-    }
+    /// The api version reported by the kernel driver, 0 if it is unknown.
+    pub driver_version: u32,
+}
 
-    /// Return the current PCIe link width.
+impl DeviceInfo {
+    /// Return the current PCIe link width, 0 if it is unknown.
     pub fn pcie_current_link_width(&self) -> u32 {
-        self.pcie_link_width("current")
+        self.pcie_current_link_width
     }
 
-    /// Return the current PCIe link generation.
+    /// Return the current PCIe link generation, 0 if it is unknown.
     pub fn pcie_current_link_gen(&self) -> i32 {
-        self.pcie_link_gen("current")
+        self.pcie_current_link_gen
     }
 
-    /// Return the maximum PCIe link width.
+    /// Return the maximum PCIe link width, 0 if it is unknown.
     pub fn pcie_max_link_width(&self) -> u32 {
-        self.pcie_link_width("max")
+        self.pcie_max_link_width
     }
 
-    /// Return the maximum PCIe link generation.
+    /// Return the maximum PCIe link generation, 0 if it is unknown.
     pub fn pcie_max_link_gen(&self) -> i32 {
-        self.pcie_link_gen("max")
+        self.pcie_max_link_gen
     }
 }
 
@@ -237,6 +373,7 @@ impl<T: Clone + Send + 'static> ChipInterface for CallbackStorage<T> {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
         (self.callback)(
             &self.user_data,
@@ -245,6 +382,7 @@ impl<T: Clone + Send + 'static> ChipInterface for CallbackStorage<T> {
                 addr,
                 data: data.as_ptr(),
                 len: data.len() as u64,
+                config: config.clone(),
             }),
         )
     }
@@ -270,6 +408,7 @@ impl<T: Clone + Send + 'static> ChipInterface for CallbackStorage<T> {
                     data: data.as_mut_ptr(),
                     len: data.len() as u64,
                 },
+                timeout: None,
             }),
         )
     }
@@ -295,6 +434,7 @@ impl<T: Clone + Send + 'static> ChipInterface for CallbackStorage<T> {
                     data: data.as_ptr(),
                     len: data.len() as u64,
                 },
+                timeout: None,
             }),
         )
     }
@@ -305,6 +445,7 @@ impl<T: Clone + Send + 'static> ChipInterface for CallbackStorage<T> {
         noc_id: u8,
         addr: u64,
         data: &[u8],
+        config: &BroadcastConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
         (self.callback)(
             &self.user_data,
@@ -315,7 +456,113 @@ impl<T: Clone + Send + 'static> ChipInterface for CallbackStorage<T> {
                     addr,
                     data: data.as_ptr(),
                     len: data.len() as u64,
+                    config: config.clone(),
                 },
+                timeout: None,
+            }),
+        )
+    }
+
+    fn axi_rmw32(
+        &self,
+        addr: u32,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        let mut result = 0;
+        (self.callback)(
+            &self.user_data,
+            FnOptions::Axi(FnAxi::Rmw32 {
+                addr,
+                mask,
+                value,
+                result: &mut result,
+            }),
+        )?;
+
+        Ok(result)
+    }
+
+    fn noc_rmw32(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        let mut result = 0;
+        (self.callback)(
+            &self.user_data,
+            FnOptions::Noc(FnNoc::Rmw32 {
+                noc_id,
+                x: x as u32,
+                y: y as u32,
+                addr,
+                mask,
+                value,
+                result: &mut result,
+            }),
+        )?;
+
+        Ok(result)
+    }
+
+    fn eth_noc_rmw32(
+        &self,
+        eth_addr: EthAddr,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        mask: u32,
+        value: u32,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        let mut result = 0;
+        (self.callback)(
+            &self.user_data,
+            FnOptions::Eth(FnRemote {
+                addr: eth_addr,
+                rw: FnNoc::Rmw32 {
+                    noc_id,
+                    x: x as u32,
+                    y: y as u32,
+                    addr,
+                    mask,
+                    value,
+                    result: &mut result,
+                },
+                timeout: None,
+            }),
+        )?;
+
+        Ok(result)
+    }
+
+    fn eth_noc_read_timeout(
+        &self,
+        eth_addr: EthAddr,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        data: &mut [u8],
+        timeout: std::time::Duration,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        (self.callback)(
+            &self.user_data,
+            FnOptions::Eth(FnRemote {
+                addr: eth_addr,
+                rw: FnNoc::Read {
+                    noc_id,
+                    x: x as u32,
+                    y: y as u32,
+                    addr,
+                    data: data.as_mut_ptr(),
+                    len: data.len() as u64,
+                },
+                timeout: Some(timeout),
             }),
         )
     }
@@ -324,3 +571,95 @@ impl<T: Clone + Send + 'static> ChipInterface for CallbackStorage<T> {
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use luwen_core::Arch;
+
+    use super::BroadcastConfig;
+    use crate::{error::NocError, HarvestingMask};
+
+    #[test]
+    fn broadcast_rectangles() {
+        let range = BroadcastConfig::default_range(Arch::Wormhole).unwrap();
+        assert_eq!(range, ((1, 0), (9, 11)));
+        assert_eq!(BroadcastConfig::default().rectangles(range), vec![range]);
+
+        let harvesting = HarvestingMask::new(Arch::Wormhole, 0b10).unwrap();
+        let config = BroadcastConfig::default().exclude_harvested(0, &harvesting);
+        assert_eq!(
+            config.rectangles(range),
+            vec![((1, 0), (9, 0)), ((1, 2), (9, 11))]
+        );
+
+        let config = BroadcastConfig::default().exclude_harvested(1, &harvesting);
+        assert_eq!(
+            config.rectangles(range),
+            vec![((1, 0), (9, 9)), ((1, 11), (9, 11))]
+        );
+    }
+
+    #[test]
+    fn broadcast_validate() {
+        let harvesting = HarvestingMask::new(Arch::Wormhole, 0b10).unwrap();
+
+        let config = BroadcastConfig {
+            start: Some((1, 2)),
+            end: Some((9, 11)),
+            ..Default::default()
+        };
+        assert!(config
+            .validate(0, Arch::Wormhole, Some(&harvesting))
+            .is_ok());
+
+        let config = BroadcastConfig {
+            start: Some((1, 0)),
+            end: Some((4, 1)),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(0, Arch::Wormhole, Some(&harvesting)),
+            Err(NocError::HarvestedCoord { x: 1, y: 1, .. })
+        ));
+        assert!(config
+            .exclude_harvested(0, &harvesting)
+            .validate(0, Arch::Wormhole, Some(&harvesting))
+            .is_ok());
+
+        let config = BroadcastConfig {
+            start: Some((5, 0)),
+            end: Some((10, 11)),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(0, Arch::Wormhole, None),
+            Err(NocError::InvalidCoord { x: 10, y: 11, .. })
+        ));
+    }
+
+    #[test]
+    fn broadcast_grayskull() {
+        assert!(BroadcastConfig::default()
+            .validate(0, Arch::Grayskull, None)
+            .is_ok());
+
+        let config = BroadcastConfig {
+            start: Some((1, 1)),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(0, Arch::Grayskull, None),
+            Err(NocError::Unsupported(_))
+        ));
+
+        let config = BroadcastConfig {
+            exclude_rows: vec![6],
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.check_arch(Arch::Grayskull),
+            Err(NocError::Unsupported(_))
+        ));
+        assert!(config.check_arch(Arch::Wormhole).is_ok());
+    }
+}
@@ -9,12 +9,22 @@ mod arc_msg;
 pub mod chip;
 mod detect_chips;
 pub mod error;
+mod ethernet_map;
+mod harvesting;
 mod interface;
+mod noc_coord;
 
 pub use arc_msg::{
-    ArcMsg, ArcMsgError, ArcMsgOk, ArcMsgProtocolError, ArcState, PowerState, TypedArcMsg,
+    ArcMsg, ArcMsgError, ArcMsgOk, ArcMsgProtocolError, ArcState, FwType, PowerState, TypedArcMsg,
 };
 pub use chip::eth_addr::{EthAddr, IntoChip};
 pub use chip::ChipImpl;
-pub use detect_chips::{detect_chips, detect_chips_silent, ChipDetectOptions, UninitChip};
-pub use interface::{CallbackStorage, DeviceInfo, FnAxi, FnDriver, FnNoc, FnOptions, FnRemote};
+pub use detect_chips::{
+    detect_chips, detect_chips_silent, detect_chips_streaming, ChipDetectOptions, UninitChip,
+};
+pub use ethernet_map::{EthernetConnection, EthernetMap, EthernetMapChip};
+pub use harvesting::HarvestingMask;
+pub use interface::{
+    BroadcastConfig, CallbackStorage, DeviceInfo, FnAxi, FnDriver, FnNoc, FnOptions, FnRemote,
+};
+pub use noc_coord::NocCoord;
@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use luwen_core::Arch;
+
+use crate::{error::NocError, HarvestingMask};
+
+/// The location of a tile on the noc.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NocCoord {
+    pub x: u8,
+    pub y: u8,
+}
+
+impl NocCoord {
+    pub fn new(x: u8, y: u8) -> Self {
+        Self { x, y }
+    }
+
+    /// Check that this coordinate on `noc_id` exists on a chip of the given arch.
+    /// If `grid` is given then harvested tensix cores are also rejected.
+    /// Coordinates can't be checked for an unknown arch, so they are always accepted.
+    pub fn validate(
+        &self,
+        noc_id: u8,
+        arch: Arch,
+        grid: Option<&HarvestingMask>,
+    ) -> Result<(), NocError> {
+        let Some((size_x, size_y)) = arch.grid_size() else {
+            return Ok(());
+        };
+
+        if self.x >= size_x || self.y >= size_y {
+            return Err(NocError::InvalidCoord {
+                noc_id,
+                x: self.x as u32,
+                y: self.y as u32,
+            });
+        }
+
+        if let Some(grid) = grid {
+            // The harvesting mask is described in noc0 coordinates, noc1 runs in the opposite direction.
+            let (x, y) = if noc_id == 0 {
+                (self.x, self.y)
+            } else {
+                (size_x - 1 - self.x, size_y - 1 - self.y)
+            };
+
+            if grid.is_harvested(x, y) {
+                return Err(NocError::HarvestedCoord {
+                    noc_id,
+                    x: self.x,
+                    y: self.y,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use luwen_core::Arch;
+
+    use super::NocCoord;
+    use crate::{error::NocError, HarvestingMask};
+
+    #[test]
+    fn validate_coord() {
+        let harvesting = HarvestingMask::new(Arch::Wormhole, 0b1).unwrap();
+
+        assert!(NocCoord::new(9, 11)
+            .validate(0, Arch::Wormhole, None)
+            .is_ok());
+        assert!(matches!(
+            NocCoord::new(10, 0).validate(0, Arch::Wormhole, None),
+            Err(NocError::InvalidCoord { .. })
+        ));
+        assert!(matches!(
+            NocCoord::new(1, 11).validate(0, Arch::Wormhole, Some(&harvesting)),
+            Err(NocError::HarvestedCoord { .. })
+        ));
+        // (8, 0) on noc1 is (1, 11) on noc0
+        assert!(matches!(
+            NocCoord::new(8, 0).validate(1, Arch::Wormhole, Some(&harvesting)),
+            Err(NocError::HarvestedCoord { .. })
+        ));
+        assert!(NocCoord::new(1, 11)
+            .validate(1, Arch::Wormhole, Some(&harvesting))
+            .is_ok());
+    }
+}
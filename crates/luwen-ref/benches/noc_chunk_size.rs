@@ -0,0 +1,60 @@
+// SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use luwen_core::Arch;
+use luwen_ref::{ExtendedPciDevice, PciDevice, Tlb};
+
+const TRANSFER_SIZE: usize = 4 << 20;
+
+fn noc_chunk_size_bench(c: &mut Criterion) {
+    // This talks to real hardware, so there is nothing to measure on a machine without a card.
+    let Some(interface) = PciDevice::scan().into_iter().next() else {
+        eprintln!("No tenstorrent devices found, skipping noc chunk size benchmark");
+        return;
+    };
+    let device = ExtendedPciDevice::open(interface).unwrap();
+    let mut device = device.borrow_mut();
+
+    // Read from the start of the first dram core so that nothing on the chip is modified.
+    let (x, y) = match device.device.arch {
+        Arch::Grayskull => (1, 0),
+        _ => (0, 0),
+    };
+    let tlb_index = device.default_tlb;
+    let max = device.max_noc_transfer_size().unwrap();
+
+    device
+        .setup_tlb(
+            tlb_index,
+            Tlb {
+                local_offset: 0,
+                x_end: x,
+                y_end: y,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let mut data = vec![0u8; TRANSFER_SIZE];
+
+    let mut group = c.benchmark_group("noc_read_chunked");
+    group.throughput(Throughput::Bytes(TRANSFER_SIZE as u64));
+    for chunk_size in [4 << 10, 64 << 10, 1 << 20, max] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(chunk_size),
+            &chunk_size,
+            |b, &chunk_size| {
+                b.iter(|| {
+                    device
+                        .noc_read_chunked(tlb_index, 0, &mut data, Some(chunk_size))
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, noc_chunk_size_bench);
+criterion_main!(benches);
@@ -31,12 +31,7 @@ pub fn detect_chips_options(options: ChipDetectOptions) -> Result<Vec<UninitChip
         // important that we let the detect function know
 
         // Hack(drosen): Basic init procedure should resolve this
-        let scratch_0 = if chip.get_arch().is_blackhole() {
-            "arc_ss.reset_unit.SCRATCH_0"
-        } else {
-            "ARC_RESET.SCRATCH[0]"
-        };
-        let result = chip.axi_sread32(scratch_0);
+        let result = chip.axi_sread32(luwen_if::chip::arc_scratch_register(chip.get_arch()));
         if let Err(err) = result {
             // Basic comms have failed... we should output a nice error message on the console
             failed_chips.push((device_id, chip, err));
@@ -0,0 +1,161 @@
+// SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use ttkmd_if::{DmaBuffer, PciError};
+
+/// The ethernet dma buffers of a device, keyed by the (x, y) of the ethernet core that uses them.
+/// At most `capacity` buffers are assigned to a core, when a new core needs a buffer the least recently
+/// used one is taken from its core and reused.
+///
+/// The driver only frees dma buffers when the device is closed, so buffers are never dropped:
+/// a buffer that no longer has a core is kept as a spare and handed out before a new one is allocated.
+pub struct DmaBufferPool {
+    capacity: usize,
+    /// Ordered from least to most recently used.
+    buffers: Vec<((u8, u8), DmaBuffer)>,
+    spare: Vec<DmaBuffer>,
+}
+
+impl DmaBufferPool {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            buffers: Vec::with_capacity(capacity),
+            spare: Vec::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the number of buffers that are assigned to cores, the least recently used buffers
+    /// become spares if needed. The pool always keeps at least one buffer.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        if self.buffers.len() > self.capacity {
+            let evicted = self.buffers.len() - self.capacity;
+            self.spare
+                .extend(self.buffers.drain(..evicted).map(|(_, buffer)| buffer));
+        }
+    }
+
+    /// The number of buffers that are assigned to a core.
+    pub fn len(&self) -> usize {
+        self.buffers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+
+    /// The total number of buffers held by the pool, including spares.
+    pub fn allocated(&self) -> usize {
+        self.buffers.len() + self.spare.len()
+    }
+
+    /// Return the buffer for `key`. If `key` has no buffer, a spare of at least `size` bytes is reused
+    /// or `allocate` is called to create one.
+    pub fn get_or_allocate(
+        &mut self,
+        key: (u8, u8),
+        size: u64,
+        allocate: impl FnOnce() -> Result<DmaBuffer, PciError>,
+    ) -> Result<&mut DmaBuffer, PciError> {
+        let entry = match self.buffers.iter().position(|(k, _)| *k == key) {
+            Some(index) => self.buffers.remove(index),
+            None => {
+                if self.buffers.len() >= self.capacity {
+                    let (_, evicted) = self.buffers.remove(0);
+                    self.spare.push(evicted);
+                }
+
+                let buffer = match self.spare.iter().position(|buffer| buffer.size >= size) {
+                    Some(index) => self.spare.swap_remove(index),
+                    None => allocate()?,
+                };
+                (key, buffer)
+            }
+        };
+        self.buffers.push(entry);
+
+        // SAFETY: An entry was pushed just above.
+        Ok(unsafe { &mut self.buffers.last_mut().unwrap_unchecked().1 })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DmaBufferPool;
+    use ttkmd_if::DmaBuffer;
+
+    fn buffer(physical_address: u64, size: u64) -> DmaBuffer {
+        DmaBuffer {
+            buffer: memmap2::MmapMut::map_anon(size as usize).unwrap(),
+            physical_address,
+            size,
+        }
+    }
+
+    #[test]
+    fn reuses_evicted_buffers() {
+        let mut pool = DmaBufferPool::new(2);
+        let mut next = 0;
+        let mut get = |pool: &mut DmaBufferPool, key| {
+            pool.get_or_allocate(key, 4096, || {
+                next += 1;
+                Ok(buffer(next, 4096))
+            })
+            .unwrap()
+            .physical_address
+        };
+
+        assert_eq!(get(&mut pool, (0, 0)), 1);
+        assert_eq!(get(&mut pool, (1, 0)), 2);
+        assert_eq!(get(&mut pool, (0, 0)), 1);
+
+        // (1, 0) is the least recently used, its buffer moves to (2, 0).
+        assert_eq!(get(&mut pool, (2, 0)), 2);
+        assert_eq!(get(&mut pool, (1, 0)), 1);
+
+        for key in 0..16 {
+            get(&mut pool, (key, 1));
+        }
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.allocated(), 2);
+    }
+
+    #[test]
+    fn shrinking_keeps_spares() {
+        let mut pool = DmaBufferPool::new(4);
+        for key in 0..4 {
+            pool.get_or_allocate((key, 0), 4096, || Ok(buffer(key as u64, 4096)))
+                .unwrap();
+        }
+
+        pool.set_capacity(0);
+        assert_eq!(pool.capacity(), 1);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.allocated(), 4);
+
+        pool.set_capacity(4);
+        for key in 4..7 {
+            pool.get_or_allocate((key, 0), 4096, || panic!("a spare should be reused"))
+                .unwrap();
+        }
+        assert_eq!(pool.allocated(), 4);
+    }
+
+    #[test]
+    fn small_spares_are_not_reused() {
+        let mut pool = DmaBufferPool::new(1);
+        pool.get_or_allocate((0, 0), 4096, || Ok(buffer(0, 4096)))
+            .unwrap();
+
+        let buffer = pool
+            .get_or_allocate((1, 0), 8192, || Ok(buffer(1, 8192)))
+            .unwrap();
+        assert_eq!(buffer.physical_address, 1);
+        assert_eq!(pool.allocated(), 2);
+    }
+}
@@ -1,7 +1,11 @@
 // SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use luwen_if::{chip::AxiError, error::PlatformError, ArcMsgError};
+use luwen_if::{
+    chip::AxiError,
+    error::{NocError, PlatformError},
+    ArcMsgError,
+};
 use thiserror::Error;
 use ttkmd_if::{PciError, PciOpenError};
 
@@ -16,6 +20,9 @@ pub enum LuwenError {
     #[error(transparent)]
     PciError(#[from] PciError),
 
+    #[error(transparent)]
+    NocError(#[from] NocError),
+
     #[error("{0}")]
     Custom(String),
 }
@@ -1,24 +1,26 @@
 // SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{
-    collections::{hash_map::Entry, HashMap},
-    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
-};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use error::LuwenError;
-use luwen_if::{FnDriver, FnOptions};
+use luwen_if::{error::NocError, FnDriver, FnOptions};
 use ttkmd_if::PciError;
 
 mod detect;
+mod dma_pool;
 pub mod error;
 mod wormhole;
 
 use wormhole::ethernet::{self, EthCommCoord};
 
 pub use detect::{detect_chips, detect_chips_fallible, detect_local_chips};
+pub use dma_pool::DmaBufferPool;
 pub use ttkmd_if::{DmaBuffer, DmaConfig, PciDevice, Tlb};
 
+/// A shared handle to a device, clones refer to the same device.
+/// Every comms op holds the write lock for its whole tlb setup + transfer sequence,
+/// so the handle can be used from multiple threads at once.
 #[derive(Clone)]
 pub struct ExtendedPciDeviceWrapper {
     inner: Arc<RwLock<ExtendedPciDevice>>,
@@ -43,12 +45,21 @@ pub struct ExtendedPciDevice {
 
     pub eth_x: u8,
     pub eth_y: u8,
+    /// The validated address of the ethernet command queues, 0 if it has not been read yet.
     pub command_q_addr: u32,
+    /// The address in ethernet core L1 that holds the address of the ethernet command queues.
+    pub command_q_ptr_addr: u32,
     pub fake_block: bool,
 
     pub default_tlb: u32,
+    /// The ordering used by noc reads and writes that go through the default tlb.
+    pub noc_ordering: ttkmd_if::tlb::Ordering,
 
-    pub ethernet_dma_buffer: HashMap<(u8, u8), DmaBuffer>,
+    /// How long to wait for an ethernet read or write to complete before giving up.
+    pub eth_timeout: std::time::Duration,
+
+    /// The 1 MB dma buffers used for ethernet block reads and writes.
+    pub ethernet_dma_buffer: DmaBufferPool,
 }
 
 impl ExtendedPciDevice {
@@ -60,7 +71,71 @@ impl ExtendedPciDevice {
         ttkmd_if::tlb::get_tlb(&self.device, index)
     }
 
+    /// The size of the window mapped by the tlb at `index`.
+    pub fn tlb_size(&self, index: u32) -> Option<u64> {
+        ttkmd_if::tlb::get_tlb_info(&self.device).tlb_size(index)
+    }
+
+    /// The largest block that noc_read/noc_write can move through the default tlb
+    /// before the window has to be reprogrammed, None if the default tlb is out of range.
+    pub fn max_noc_transfer_size(&self) -> Option<u64> {
+        self.tlb_size(self.default_tlb)
+    }
+
+    /// Called when a noc access through `tlb_index` fails. If the failure looks like a hung access
+    /// the tlb is pointed back at a plain unicast window, so that a single bad access does not
+    /// cause every later access through the same tlb to fail as well.
+    fn recover_tlb(&mut self, tlb_index: u32, err: PciError) -> PciError {
+        if matches!(
+            err,
+            PciError::BrokenConnection
+                | PciError::DmaTimeout { .. }
+                | PciError::DmaChunkFailed { .. }
+        ) {
+            // The original error is the useful one, so a failure to reset is ignored.
+            let _ = self.setup_tlb(tlb_index, Tlb::default());
+        }
+
+        err
+    }
+
     pub fn noc_write(&mut self, tlb_index: u32, addr: u64, data: &[u8]) -> Result<(), PciError> {
+        self.noc_write_windowed(tlb_index, addr, data, u64::MAX)
+    }
+
+    /// Same as noc_write, but the tlb is reprogrammed at least every `chunk_size` bytes
+    /// rather than only when the end of the window is reached.
+    pub fn noc_write_chunked(
+        &mut self,
+        tlb_index: u32,
+        addr: u64,
+        data: &[u8],
+        chunk_size: Option<u64>,
+    ) -> Result<(), LuwenError> {
+        let chunk_size = match chunk_size {
+            Some(0) => {
+                return Err(LuwenError::Custom(
+                    "noc transfer chunk size must be non-zero".to_string(),
+                ))
+            }
+            Some(chunk_size) => chunk_size,
+            None => u64::MAX,
+        };
+
+        Ok(self.noc_write_windowed(tlb_index, addr, data, chunk_size)?)
+    }
+
+    #[cfg_attr(
+        feature = "comms-tracing",
+        tracing::instrument(level = "trace", skip(self, data), fields(len = data.len()))
+    )]
+    fn noc_write_windowed(
+        &mut self,
+        tlb_index: u32,
+        addr: u64,
+        data: &[u8],
+        chunk_size: u64,
+    ) -> Result<(), PciError> {
         let mut written = 0;
 
         let mut starting_tlb = self.get_tlb(tlb_index)?;
@@ -71,11 +146,12 @@ impl ExtendedPciDevice {
             starting_tlb.local_offset = addr + written;
             let (bar_addr, slice_len) = self.setup_tlb(tlb_index, starting_tlb.clone())?;
 
-            let to_write = std::cmp::min(slice_len, len.saturating_sub(written));
+            let to_write = slice_len.min(chunk_size).min(len.saturating_sub(written));
             self.write_block(
                 bar_addr as u32,
                 &data[written as usize..(written as usize + to_write as usize)],
-            )?;
+            )
+            .map_err(|err| self.recover_tlb(tlb_index, err))?;
 
             written += to_write;
         }
@@ -84,6 +160,42 @@ impl ExtendedPciDevice {
     }
 
     pub fn noc_read(&mut self, tlb_index: u32, addr: u64, data: &mut [u8]) -> Result<(), PciError> {
+        self.noc_read_windowed(tlb_index, addr, data, u64::MAX)
+    }
+
+    /// Same as noc_read, but the tlb is reprogrammed at least every `chunk_size` bytes
+    /// rather than only when the end of the window is reached.
+    pub fn noc_read_chunked(
+        &mut self,
+        tlb_index: u32,
+        addr: u64,
+        data: &mut [u8],
+        chunk_size: Option<u64>,
+    ) -> Result<(), LuwenError> {
+        let chunk_size = match chunk_size {
+            Some(0) => {
+                return Err(LuwenError::Custom(
+                    "noc transfer chunk size must be non-zero".to_string(),
+                ))
+            }
+            Some(chunk_size) => chunk_size,
+            None => u64::MAX,
+        };
+
+        Ok(self.noc_read_windowed(tlb_index, addr, data, chunk_size)?)
+    }
+
+    #[cfg_attr(
+        feature = "comms-tracing",
+        tracing::instrument(level = "trace", skip(self, data), fields(len = data.len()))
+    )]
+    fn noc_read_windowed(
+        &mut self,
+        tlb_index: u32,
+        addr: u64,
+        data: &mut [u8],
+        chunk_size: u64,
+    ) -> Result<(), PciError> {
         let mut read = 0;
 
         let mut starting_tlb = self.get_tlb(tlb_index)?;
@@ -94,11 +206,12 @@ impl ExtendedPciDevice {
             starting_tlb.local_offset = addr + read;
             let (bar_addr, slice_len) = self.setup_tlb(tlb_index, starting_tlb.clone())?;
 
-            let to_read = std::cmp::min(slice_len, len.saturating_sub(read));
+            let to_read = slice_len.min(chunk_size).min(len.saturating_sub(read));
             self.read_block(
                 bar_addr as u32,
                 &mut data[read as usize..(read as usize + to_read as usize)],
-            )?;
+            )
+            .map_err(|err| self.recover_tlb(tlb_index, err))?;
 
             read += to_read;
         }
@@ -106,14 +219,72 @@ impl ExtendedPciDevice {
         Ok(())
     }
 
-    pub fn noc_write32(
+    /// Read from `addr` on core (x, y) through the default tlb with the current noc ordering.
+    /// If `chunk_size` is set the tlb is reprogrammed at least every `chunk_size` bytes.
+    pub fn noc_read_coord(
+        &mut self,
+        noc_id: u8,
+        x: u32,
+        y: u32,
+        addr: u64,
+        data: &mut [u8],
+        chunk_size: Option<u64>,
+    ) -> Result<(), LuwenError> {
+        self.setup_default_tlb(noc_id, x, y, addr)?;
+        self.noc_read_chunked(self.default_tlb, addr, data, chunk_size)
+    }
+
+    /// Write to `addr` on core (x, y) through the default tlb with the current noc ordering.
+    /// If `chunk_size` is set the tlb is reprogrammed at least every `chunk_size` bytes.
+    pub fn noc_write_coord(
+        &mut self,
+        noc_id: u8,
+        x: u32,
+        y: u32,
+        addr: u64,
+        data: &[u8],
+        chunk_size: Option<u64>,
+    ) -> Result<(), LuwenError> {
+        self.setup_default_tlb(noc_id, x, y, addr)?;
+        self.noc_write_chunked(self.default_tlb, addr, data, chunk_size)
+    }
+
+    fn setup_default_tlb(
+        &mut self,
+        noc_id: u8,
+        x: u32,
+        y: u32,
+        addr: u64,
+    ) -> Result<(), LuwenError> {
+        if x >= NOC_COORD_LIMIT || y >= NOC_COORD_LIMIT {
+            return Err(NocError::InvalidCoord { noc_id, x, y }.into());
+        }
+
+        self.setup_tlb(
+            self.default_tlb,
+            Tlb {
+                local_offset: addr,
+                x_end: x as u8,
+                y_end: y as u8,
+                noc_sel: noc_id,
+                mcast: false,
+                ordering: self.noc_ordering.clone(),
+                ..Default::default()
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Write 4 bytes to `addr` in device order.
+    pub fn noc_write32_raw(
         &mut self,
         tlb_index: u32,
         noc_id: u8,
         x: u8,
         y: u8,
         addr: u64,
-        data: u32,
+        data: [u8; 4],
     ) -> Result<(), PciError> {
         self.setup_tlb(
             tlb_index,
@@ -125,19 +296,20 @@ impl ExtendedPciDevice {
             },
         )?;
 
-        self.noc_write(tlb_index, addr, &data.to_le_bytes())?;
+        self.noc_write(tlb_index, addr, &data)?;
 
         Ok(())
     }
 
-    pub fn noc_read32(
+    /// Read the 4 bytes at `addr` in device order.
+    pub fn noc_read32_raw(
         &mut self,
         tlb_index: u32,
         noc_id: u8,
         x: u8,
         y: u8,
         addr: u64,
-    ) -> Result<u32, PciError> {
+    ) -> Result<[u8; 4], PciError> {
         self.setup_tlb(
             tlb_index,
             Tlb {
@@ -152,19 +324,54 @@ impl ExtendedPciDevice {
 
         self.noc_read(tlb_index, addr, &mut output)?;
 
-        Ok(u32::from_le_bytes(output))
+        Ok(output)
+    }
+
+    /// Write `data` as a little endian u32, the device byte order.
+    pub fn noc_write32(
+        &mut self,
+        tlb_index: u32,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        data: u32,
+    ) -> Result<(), PciError> {
+        self.noc_write32_raw(tlb_index, noc_id, x, y, addr, data.to_le_bytes())
+    }
+
+    /// Read a little endian u32, the device byte order.
+    pub fn noc_read32(
+        &mut self,
+        tlb_index: u32,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+    ) -> Result<u32, PciError> {
+        Ok(u32::from_le_bytes(
+            self.noc_read32_raw(tlb_index, noc_id, x, y, addr)?,
+        ))
     }
 }
 
 impl ExtendedPciDevice {
     pub fn open(pci_interface: usize) -> Result<ExtendedPciDeviceWrapper, ttkmd_if::PciOpenError> {
-        let device = PciDevice::open(pci_interface)?;
+        Self::from_device(PciDevice::open(pci_interface)?)
+    }
+
+    /// Same as open, but the device is forced to be `arch` rather than using its pci device id.
+    pub fn open_with_arch(
+        pci_interface: usize,
+        arch: luwen_core::Arch,
+    ) -> Result<ExtendedPciDeviceWrapper, ttkmd_if::PciOpenError> {
+        Self::from_device(PciDevice::open_with_arch(pci_interface, arch)?)
+    }
 
+    fn from_device(device: PciDevice) -> Result<ExtendedPciDeviceWrapper, ttkmd_if::PciOpenError> {
         let (grid_size_x, grid_size_y) = match device.arch {
-            luwen_core::Arch::Grayskull => (13, 12),
-            luwen_core::Arch::Wormhole => (10, 12),
-            luwen_core::Arch::Blackhole => (17, 12),
             luwen_core::Arch::Unknown(id) => unreachable!("Found unrecognizable id {id:x}"),
+            arch => arch.grid_size().unwrap(),
         };
 
         Ok(ExtendedPciDeviceWrapper {
@@ -175,6 +382,7 @@ impl ExtendedPciDevice {
                 eth_x: 4,
                 eth_y: 6,
                 command_q_addr: 0,
+                command_q_ptr_addr: 0x170,
                 fake_block: false,
 
                 default_tlb: match device.arch {
@@ -182,14 +390,47 @@ impl ExtendedPciDevice {
                     luwen_core::Arch::Blackhole => 190,
                     luwen_core::Arch::Unknown(id) => unreachable!("Found unrecognizable id {id:x}"),
                 },
+                noc_ordering: ttkmd_if::tlb::Ordering::default(),
+
+                eth_timeout: std::time::Duration::from_secs(5 * 60),
 
                 device,
 
-                ethernet_dma_buffer: HashMap::with_capacity(16),
+                // Enough to keep a buffer for every ethernet core on a wormhole.
+                ethernet_dma_buffer: DmaBufferPool::new(16),
             })),
         })
     }
 
+    /// Read the address of the command queues of the ethernet core at (eth_x, eth_y)
+    /// from `command_q_ptr_addr` and check that it looks like a valid queue.
+    /// The address is cached in `command_q_addr` until it is reset to 0.
+    pub fn eth_command_q_addr(&mut self) -> Result<u32, LuwenError> {
+        if self.command_q_addr != 0 {
+            return Ok(self.command_q_addr);
+        }
+
+        let (eth_x, eth_y) = (self.eth_x, self.eth_y);
+        let default_tlb = self.default_tlb;
+
+        let command_q_addr = noc_read32(
+            &mut self.device,
+            default_tlb,
+            0,
+            eth_x,
+            eth_y,
+            self.command_q_ptr_addr,
+        )?;
+        ethernet::validate_command_queue(
+            &mut self.device,
+            |device: &mut _, addr| noc_read32(device, default_tlb, 0, eth_x, eth_y, addr),
+            command_q_addr,
+        )?;
+        self.command_q_addr = command_q_addr;
+
+        Ok(command_q_addr)
+    }
+
     pub fn read_block(&mut self, addr: u32, data: &mut [u8]) -> Result<(), PciError> {
         self.device.read_block(addr, data)
     }
@@ -250,13 +491,43 @@ fn noc_read32(
     Ok(u32::from_le_bytes(data))
 }
 
+/// Noc coordinates are 6 bits wide on every supported arch.
+const NOC_COORD_LIMIT: u32 = 1 << 6;
+
 pub fn comms_callback(
     ud: &ExtendedPciDeviceWrapper,
     op: FnOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    Ok(comms_callback_inner(ud, op)?)
+    let is_noc = matches!(op, FnOptions::Noc(_) | FnOptions::Eth(_));
+    match comms_callback_inner(ud, op) {
+        Ok(()) => Ok(()),
+        Err(err) if is_noc => Err(noc_access_error(err)),
+        // Noc errors are passed back unwrapped so that callers can downcast to them.
+        Err(LuwenError::NocError(err)) => Err(err.into()),
+        Err(err) => Err(err.into()),
+    }
 }
 
+/// Convert the error from a noc access into the error that is passed back through the chip interface.
+/// Pci errors that have a noc meaning are reported as the matching NocError, which is passed back
+/// unwrapped so that callers can downcast to it.
+pub fn noc_access_error(err: LuwenError) -> Box<dyn std::error::Error> {
+    match err {
+        LuwenError::NocError(err) => err.into(),
+        LuwenError::PciError(PciError::TlbOutOfRange { id }) => {
+            NocError::TlbUnavailable { index: id as u32 }.into()
+        }
+        LuwenError::PciError(err @ PciError::BrokenConnection) => {
+            NocError::HardwareFault(err.to_string()).into()
+        }
+        err => err.into(),
+    }
+}
+
+#[cfg_attr(
+    feature = "comms-tracing",
+    tracing::instrument(level = "trace", skip(ud), err)
+)]
 pub fn comms_callback_inner(
     ud: &ExtendedPciDeviceWrapper,
     op: FnOptions,
@@ -279,6 +550,13 @@ pub fn comms_callback_inner(
                             device_id: borrow.device.physical.device_id,
                             board_id: borrow.device.physical.subsystem_id,
                             bar_size: borrow.device.physical.bar_size_bytes,
+
+                            pcie_current_link_width: borrow.device.physical.pcie_current_link_width,
+                            pcie_current_link_gen: borrow.device.physical.pcie_current_link_gen,
+                            pcie_max_link_width: borrow.device.physical.pcie_max_link_width,
+                            pcie_max_link_gen: borrow.device.physical.pcie_max_link_gen,
+
+                            driver_version: borrow.device.driver_version,
                         });
                     }
                 }
@@ -305,6 +583,10 @@ pub fn comms_callback_inner(
             }
             luwen_if::FnAxi::Write { addr, data, len } => {
                 if len > 0 {
+                    // Hold the lock for the whole write so that a partial write can't race
+                    // with another write to the same word between the read and the writeback.
+                    let mut writer = ud.borrow_mut();
+
                     // Assuming here that u32 is our fundamental unit of transfer
                     if len <= 4 {
                         let to_write = if len == 4 {
@@ -313,7 +595,7 @@ pub fn comms_callback_inner(
                         } else {
                             // We are reading less than a u32, so we need to read the existing value first
                             // then writeback the new value with the lower len bytes replaced
-                            let value = ud.borrow_mut().device.read32(addr)?;
+                            let value = writer.device.read32(addr)?;
                             let mut value = value.to_le_bytes();
                             unsafe {
                                 value
@@ -324,14 +606,42 @@ pub fn comms_callback_inner(
                             u32::from_le_bytes(value)
                         };
 
-                        ud.borrow_mut().device.write32(addr, to_write)?;
+                        writer.device.write32(addr, to_write)?;
                     } else {
                         unsafe {
-                            ud.borrow_mut()
+                            writer
                                 .write_block(addr, std::slice::from_raw_parts(data, len as usize))?
                         };
                     }
+
+                    // Writes through the wc mapping can be held in the cpu write buffer,
+                    // flush them so that config sequences reach the chip in order.
+                    let device = &writer.device;
+                    if device.is_wc_addr(addr) {
+                        device.flush_wc();
+                    }
+                }
+            }
+            luwen_if::FnAxi::Rmw32 {
+                addr,
+                mask,
+                value,
+                result,
+            } => {
+                // The read and the writeback are done under the same borrow so that no other
+                // access through this device can land in between.
+                let mut writer = ud.borrow_mut();
+
+                let current = writer.device.read32(addr)?;
+                let value = (current & !mask) | (value & mask);
+                writer.device.write32(addr, value)?;
+
+                let device = &writer.device;
+                if device.is_wc_addr(addr) {
+                    device.flush_wc();
                 }
+
+                unsafe { *result = value };
             }
         },
         FnOptions::Noc(op) => match op {
@@ -343,24 +653,14 @@ pub fn comms_callback_inner(
                 data,
                 len,
             } => {
-                let mut reader = ud.borrow_mut();
-                let reader: &mut ExtendedPciDevice = &mut reader;
-
-                reader.setup_tlb(
-                    reader.default_tlb,
-                    Tlb {
-                        local_offset: addr,
-                        x_end: x as u8,
-                        y_end: y as u8,
-                        noc_sel: noc_id,
-                        mcast: false,
-                        ..Default::default()
-                    },
+                ud.borrow_mut().noc_read_coord(
+                    noc_id,
+                    x,
+                    y,
+                    addr,
+                    unsafe { std::slice::from_raw_parts_mut(data, len as usize) },
+                    None,
                 )?;
-
-                reader.noc_read(reader.default_tlb, addr, unsafe {
-                    std::slice::from_raw_parts_mut(data, len as usize)
-                })?;
             }
             luwen_if::FnNoc::Write {
                 noc_id,
@@ -370,239 +670,323 @@ pub fn comms_callback_inner(
                 data,
                 len,
             } => {
-                let mut writer = ud.borrow_mut();
-                let writer: &mut ExtendedPciDevice = &mut writer;
-
-                writer.setup_tlb(
-                    writer.default_tlb,
-                    Tlb {
-                        local_offset: addr,
-                        x_end: x as u8,
-                        y_end: y as u8,
-                        noc_sel: noc_id,
-                        mcast: false,
-                        ..Default::default()
-                    },
+                ud.borrow_mut().noc_write_coord(
+                    noc_id,
+                    x,
+                    y,
+                    addr,
+                    unsafe { std::slice::from_raw_parts(data, len as usize) },
+                    None,
                 )?;
-
-                writer.noc_write(writer.default_tlb, addr, unsafe {
-                    std::slice::from_raw_parts(data, len as usize)
-                })?;
             }
-            luwen_if::FnNoc::Broadcast {
+            luwen_if::FnNoc::Rmw32 {
                 noc_id,
+                x,
+                y,
                 addr,
-                data,
-                len,
+                mask,
+                value,
+                result,
             } => {
                 let mut writer = ud.borrow_mut();
                 let writer: &mut ExtendedPciDevice = &mut writer;
 
-                let (x_start, y_start) = match writer.device.arch {
-                    luwen_core::Arch::Grayskull => (0, 0),
-                    luwen_core::Arch::Wormhole => (1, 0),
-                    luwen_core::Arch::Blackhole => (0, 1),
-                    luwen_core::Arch::Unknown(_) => todo!(),
-                };
-
-                writer.setup_tlb(
-                    writer.default_tlb,
-                    Tlb {
-                        local_offset: addr,
-                        x_start,
-                        y_start,
-                        x_end: writer.grid_size_x - 1,
-                        y_end: writer.grid_size_y - 1,
-                        noc_sel: noc_id,
-                        mcast: true,
-                        ..Default::default()
-                    },
-                )?;
+                writer.setup_default_tlb(noc_id, x, y, addr)?;
+
+                let mut current = [0u8; 4];
+                writer.noc_read(writer.default_tlb, addr, &mut current)?;
+                let value = (u32::from_le_bytes(current) & !mask) | (value & mask);
+                writer.noc_write(writer.default_tlb, addr, &value.to_le_bytes())?;
 
-                writer.noc_write(writer.default_tlb, addr, unsafe {
-                    std::slice::from_raw_parts(data, len as usize)
-                })?;
+                unsafe { *result = value };
             }
-        },
-        FnOptions::Eth(op) => match op.rw {
-            luwen_if::FnNoc::Read {
+            luwen_if::FnNoc::Broadcast {
                 noc_id,
-                x,
-                y,
                 addr,
                 data,
                 len,
+                config,
             } => {
-                let mut borrow = ud.borrow_mut();
-                let borrow: &mut ExtendedPciDevice = &mut borrow;
-
-                let eth_x = borrow.eth_x;
-                let eth_y = borrow.eth_y;
-
-                let command_q_addr = noc_read32(
-                    &mut borrow.device,
-                    borrow.default_tlb,
-                    0,
-                    eth_x,
-                    eth_y,
-                    0x170,
-                )?;
-                let fake_block = borrow.fake_block;
-
-                let default_tlb = borrow.default_tlb;
-                let read32 =
-                    |borrow: &mut _, addr| noc_read32(borrow, default_tlb, 0, eth_x, eth_y, addr);
-
-                let write32 = |borrow: &mut _, addr, data| {
-                    noc_write32(borrow, default_tlb, 0, eth_x, eth_y, addr, data)
-                };
-
-                let dma_buffer = {
-                    let key = (eth_x, eth_y);
-                    if let Entry::Vacant(e) = borrow.ethernet_dma_buffer.entry(key) {
-                        // 1 MB buffer
-                        e.insert(borrow.device.allocate_dma_buffer(1 << 20)?);
-                    }
+                let mut writer = ud.borrow_mut();
+                let writer: &mut ExtendedPciDevice = &mut writer;
 
-                    // SAFETY: Can never get here without first inserting something into the hashmap
-                    unsafe { borrow.ethernet_dma_buffer.get_mut(&key).unwrap_unchecked() }
-                };
-
-                ethernet::fixup_queues(&mut borrow.device, read32, write32, command_q_addr)?;
-
-                if len <= 4 {
-                    let value = ethernet::eth_read32(
-                        &mut borrow.device,
-                        read32,
-                        write32,
-                        command_q_addr,
-                        EthCommCoord {
-                            coord: op.addr,
-                            noc_id,
-                            noc_x: x as u8,
-                            noc_y: y as u8,
-                            offset: addr,
+                let default_range = luwen_if::BroadcastConfig::default_range(writer.device.arch)
+                    .ok_or_else(|| {
+                        LuwenError::Custom(format!(
+                            "Cannot broadcast on a chip with arch {}",
+                            writer.device.arch
+                        ))
+                    })?;
+
+                config.check_arch(writer.device.arch)?;
+
+                let data = unsafe { std::slice::from_raw_parts(data, len as usize) };
+                for ((x_start, y_start), (x_end, y_end)) in config.rectangles(default_range) {
+                    writer.setup_tlb(
+                        writer.default_tlb,
+                        Tlb {
+                            local_offset: addr,
+                            x_start,
+                            y_start,
+                            x_end,
+                            y_end,
+                            noc_sel: noc_id,
+                            mcast: true,
+                            ..Default::default()
                         },
-                        std::time::Duration::from_secs(5 * 60),
                     )?;
 
-                    let sl = unsafe { std::slice::from_raw_parts_mut(data, len as usize) };
-                    let vl = value.to_le_bytes();
-
-                    for (s, v) in sl.iter_mut().zip(vl.iter()) {
-                        *s = *v;
-                    }
-                } else {
-                    ethernet::block_read(
-                        &mut borrow.device,
-                        read32,
-                        write32,
-                        dma_buffer,
-                        command_q_addr,
-                        std::time::Duration::from_secs(5 * 60),
-                        fake_block,
-                        EthCommCoord {
-                            coord: op.addr,
-                            noc_id,
-                            noc_x: x as u8,
-                            noc_y: y as u8,
-                            offset: addr,
-                        },
-                        unsafe { std::slice::from_raw_parts_mut(data, len as usize) },
-                    )?;
+                    writer.noc_write(writer.default_tlb, addr, data)?;
                 }
             }
-            luwen_if::FnNoc::Write {
-                noc_id,
-                x,
-                y,
-                addr,
-                data,
-                len,
-            } => {
-                let mut borrow = ud.borrow_mut();
-                let borrow: &mut ExtendedPciDevice = &mut borrow;
-
-                let eth_x = borrow.eth_x;
-                let eth_y = borrow.eth_y;
-
-                let command_q_addr =
-                    borrow.noc_read32(borrow.default_tlb, 0, eth_x, eth_y, 0x170)?;
-                let fake_block = borrow.fake_block;
-
-                let default_tlb = borrow.default_tlb;
-                let read32 =
-                    |borrow: &mut _, addr| noc_read32(borrow, default_tlb, 0, eth_x, eth_y, addr);
-
-                let write32 = |borrow: &mut _, addr, data| {
-                    noc_write32(borrow, default_tlb, 0, eth_x, eth_y, addr, data)
-                };
+        },
+        FnOptions::Eth(op) => {
+            let result = eth_callback(ud, op);
+            if result.is_err() {
+                // The ethernet core may have been reset or retrained, find its queues again on the next access.
+                ud.borrow_mut().command_q_addr = 0;
+            }
+            result?;
+        }
+    }
 
-                let dma_buffer = {
-                    let key = (eth_x, eth_y);
-                    if let Entry::Vacant(e) = borrow.ethernet_dma_buffer.entry(key) {
-                        // 1 MB buffer
-                        e.insert(borrow.device.allocate_dma_buffer(1 << 20)?);
-                    }
+    Ok(())
+}
 
-                    // SAFETY: Can never get here without first inserting something into the hashmap
-                    unsafe { borrow.ethernet_dma_buffer.get_mut(&key).unwrap_unchecked() }
-                };
+fn eth_callback(ud: &ExtendedPciDeviceWrapper, op: luwen_if::FnRemote) -> Result<(), LuwenError> {
+    match op.rw {
+        luwen_if::FnNoc::Read {
+            noc_id,
+            x,
+            y,
+            addr,
+            data,
+            len,
+        } => {
+            let mut borrow = ud.borrow_mut();
+            let borrow: &mut ExtendedPciDevice = &mut borrow;
+
+            let eth_x = borrow.eth_x;
+            let eth_y = borrow.eth_y;
+
+            let command_q_addr = borrow.eth_command_q_addr()?;
+            let fake_block = borrow.fake_block;
+            let eth_timeout = op.timeout.unwrap_or(borrow.eth_timeout);
+
+            let default_tlb = borrow.default_tlb;
+            let read32 =
+                |borrow: &mut _, addr| noc_read32(borrow, default_tlb, 0, eth_x, eth_y, addr);
+
+            let write32 = |borrow: &mut _, addr, data| {
+                noc_write32(borrow, default_tlb, 0, eth_x, eth_y, addr, data)
+            };
+
+            let dma_buffer = {
+                let device = &mut borrow.device;
+                borrow
+                    .ethernet_dma_buffer
+                    // 1 MB buffer
+                    .get_or_allocate((eth_x, eth_y), 1 << 20, || {
+                        device.allocate_dma_buffer(1 << 20)
+                    })?
+            };
+
+            ethernet::fixup_queues(&mut borrow.device, read32, write32, command_q_addr)?;
+
+            if len <= 4 {
+                let value = ethernet::eth_read32(
+                    &mut borrow.device,
+                    read32,
+                    write32,
+                    command_q_addr,
+                    EthCommCoord {
+                        coord: op.addr,
+                        noc_id,
+                        noc_x: x as u8,
+                        noc_y: y as u8,
+                        offset: addr,
+                    },
+                    eth_timeout,
+                )?;
 
-                ethernet::fixup_queues(&mut borrow.device, read32, write32, command_q_addr)?;
+                let sl = unsafe { std::slice::from_raw_parts_mut(data, len as usize) };
+                let vl = value.to_le_bytes();
 
-                if len <= 4 {
-                    let sl = unsafe { std::slice::from_raw_parts(data, len as usize) };
-                    let mut value = 0u32;
-                    for s in sl.iter().rev() {
-                        value <<= 8;
-                        value |= *s as u32;
-                    }
-
-                    ethernet::eth_write32(
-                        &mut borrow.device,
-                        read32,
-                        write32,
-                        command_q_addr,
-                        EthCommCoord {
-                            coord: op.addr,
-                            noc_id,
-                            noc_x: x as u8,
-                            noc_y: y as u8,
-                            offset: addr,
-                        },
-                        std::time::Duration::from_secs(5 * 60),
-                        value,
-                    )?;
-                } else {
-                    ethernet::block_write(
-                        &mut borrow.device,
-                        read32,
-                        write32,
-                        dma_buffer,
-                        command_q_addr,
-                        std::time::Duration::from_secs(5 * 60),
-                        fake_block,
-                        EthCommCoord {
-                            coord: op.addr,
-                            noc_id,
-                            noc_x: x as u8,
-                            noc_y: y as u8,
-                            offset: addr,
-                        },
-                        unsafe { std::slice::from_raw_parts(data, len as usize) },
-                    )?;
+                for (s, v) in sl.iter_mut().zip(vl.iter()) {
+                    *s = *v;
                 }
+            } else {
+                ethernet::block_read(
+                    &mut borrow.device,
+                    read32,
+                    write32,
+                    dma_buffer,
+                    command_q_addr,
+                    eth_timeout,
+                    fake_block,
+                    EthCommCoord {
+                        coord: op.addr,
+                        noc_id,
+                        noc_x: x as u8,
+                        noc_y: y as u8,
+                        offset: addr,
+                    },
+                    unsafe { std::slice::from_raw_parts_mut(data, len as usize) },
+                )?;
             }
-            luwen_if::FnNoc::Broadcast {
-                noc_id,
-                addr,
-                data,
-                len,
-            } => {
-                todo!("Tried to do an ethernet broadcast which is not supported, noc_id: {}, addr: {:#x}, data: {:p}, len: {:x}", noc_id, addr, data, len);
+        }
+        luwen_if::FnNoc::Write {
+            noc_id,
+            x,
+            y,
+            addr,
+            data,
+            len,
+        } => {
+            let mut borrow = ud.borrow_mut();
+            let borrow: &mut ExtendedPciDevice = &mut borrow;
+
+            let eth_x = borrow.eth_x;
+            let eth_y = borrow.eth_y;
+
+            let command_q_addr = borrow.eth_command_q_addr()?;
+            let fake_block = borrow.fake_block;
+            let eth_timeout = op.timeout.unwrap_or(borrow.eth_timeout);
+
+            let default_tlb = borrow.default_tlb;
+            let read32 =
+                |borrow: &mut _, addr| noc_read32(borrow, default_tlb, 0, eth_x, eth_y, addr);
+
+            let write32 = |borrow: &mut _, addr, data| {
+                noc_write32(borrow, default_tlb, 0, eth_x, eth_y, addr, data)
+            };
+
+            let dma_buffer = {
+                let device = &mut borrow.device;
+                borrow
+                    .ethernet_dma_buffer
+                    // 1 MB buffer
+                    .get_or_allocate((eth_x, eth_y), 1 << 20, || {
+                        device.allocate_dma_buffer(1 << 20)
+                    })?
+            };
+
+            ethernet::fixup_queues(&mut borrow.device, read32, write32, command_q_addr)?;
+
+            if len <= 4 {
+                let sl = unsafe { std::slice::from_raw_parts(data, len as usize) };
+                let mut value = 0u32;
+                for s in sl.iter().rev() {
+                    value <<= 8;
+                    value |= *s as u32;
+                }
+
+                ethernet::eth_write32(
+                    &mut borrow.device,
+                    read32,
+                    write32,
+                    command_q_addr,
+                    EthCommCoord {
+                        coord: op.addr,
+                        noc_id,
+                        noc_x: x as u8,
+                        noc_y: y as u8,
+                        offset: addr,
+                    },
+                    eth_timeout,
+                    value,
+                )?;
+            } else {
+                ethernet::block_write(
+                    &mut borrow.device,
+                    read32,
+                    write32,
+                    dma_buffer,
+                    command_q_addr,
+                    eth_timeout,
+                    fake_block,
+                    EthCommCoord {
+                        coord: op.addr,
+                        noc_id,
+                        noc_x: x as u8,
+                        noc_y: y as u8,
+                        offset: addr,
+                    },
+                    unsafe { std::slice::from_raw_parts(data, len as usize) },
+                )?;
             }
-        },
+        }
+        luwen_if::FnNoc::Rmw32 {
+            noc_id,
+            x,
+            y,
+            addr,
+            mask,
+            value,
+            result,
+        } => {
+            let mut borrow = ud.borrow_mut();
+            let borrow: &mut ExtendedPciDevice = &mut borrow;
+
+            let eth_x = borrow.eth_x;
+            let eth_y = borrow.eth_y;
+
+            let command_q_addr = borrow.eth_command_q_addr()?;
+            let eth_timeout = op.timeout.unwrap_or(borrow.eth_timeout);
+
+            let default_tlb = borrow.default_tlb;
+            let read32 =
+                |borrow: &mut _, addr| noc_read32(borrow, default_tlb, 0, eth_x, eth_y, addr);
+
+            let write32 = |borrow: &mut _, addr, data| {
+                noc_write32(borrow, default_tlb, 0, eth_x, eth_y, addr, data)
+            };
+
+            ethernet::fixup_queues(&mut borrow.device, read32, write32, command_q_addr)?;
+
+            let coord = EthCommCoord {
+                coord: op.addr,
+                noc_id,
+                noc_x: x as u8,
+                noc_y: y as u8,
+                offset: addr,
+            };
+
+            // Both ethernet commands are issued under this borrow, so no other command from
+            // this device can be queued between the read and the writeback.
+            let current = ethernet::eth_read32(
+                &mut borrow.device,
+                read32,
+                write32,
+                command_q_addr,
+                coord.clone(),
+                eth_timeout,
+            )?;
+            let value = (current & !mask) | (value & mask);
+            ethernet::eth_write32(
+                &mut borrow.device,
+                read32,
+                write32,
+                command_q_addr,
+                coord,
+                eth_timeout,
+                value,
+            )?;
+
+            unsafe { *result = value };
+        }
+        luwen_if::FnNoc::Broadcast {
+            noc_id,
+            addr,
+            data,
+            len,
+            ..
+        } => {
+            return Err(LuwenError::Custom(format!(
+                "Tried to do an ethernet broadcast which is not supported, noc_id: {}, addr: {:#x}, data: {:p}, len: {:x}",
+                noc_id, addr, data, len
+            )));
+        }
     }
 
     Ok(())
@@ -618,3 +1002,32 @@ pub fn open(interface_id: usize) -> Result<luwen_if::chip::Chip, LuwenError> {
         luwen_if::CallbackStorage::new(comms_callback, ud.clone()),
     )?)
 }
+
+#[cfg(test)]
+mod test {
+    use luwen_if::ChipImpl;
+
+    #[test]
+    #[ignore = "requires a tenstorrent device"]
+    fn concurrent_noc_access() {
+        let chip = crate::open(crate::PciDevice::scan()[0]).unwrap();
+
+        // Each thread reads the node id of a different core, if the tlb setup of one thread
+        // interleaved with the read of another the wrong core would answer.
+        let cores: Vec<(u8, u8)> = match chip.get_harvesting() {
+            Ok(harvesting) => harvesting.enabled_tensix().into_iter().take(4).collect(),
+            Err(_) => vec![(1, 1), (2, 2), (3, 3), (4, 4)],
+        };
+
+        std::thread::scope(|scope| {
+            for &(x, y) in &cores {
+                let chip = &chip;
+                scope.spawn(move || {
+                    for _ in 0..1000 {
+                        assert_eq!(chip.read_noc_node_id(0, x, y).unwrap(), (x, y));
+                    }
+                });
+            }
+        });
+    }
+}
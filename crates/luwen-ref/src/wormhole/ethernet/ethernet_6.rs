@@ -1,7 +1,7 @@
 // SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use luwen_if::EthAddr;
+use luwen_if::{error::NocError, EthAddr};
 use ttkmd_if::PciError;
 
 use crate::error::LuwenError;
@@ -90,9 +90,10 @@ fn wait_for_idle<D>(
         }
 
         if start.elapsed() > timeout {
-            return Err(LuwenError::Custom(
+            return Err(NocError::Timeout(
                 "Ethernet timeout while waiting for command queue to be idle".to_string(),
-            ));
+            )
+            .into());
         }
         curr_wptr = read32(user_data, command_q_addr + REQ_Q_ADDR + 4 * WR_PTR_OFFSET)?;
     }
@@ -138,9 +139,10 @@ pub fn eth_read32<D>(
     while curr_wptr == curr_rptr {
         curr_wptr = read32(user_data, command_q_addr + RESP_Q_ADDR + 4 * WR_PTR_OFFSET)?;
         if start_time.elapsed() > timeout {
-            return Err(LuwenError::Custom(
+            return Err(NocError::Timeout(
                 "Ethernet timeout while waiting for read queue to be cleared".to_string(),
-            ));
+            )
+            .into());
         }
     }
 
@@ -152,9 +154,10 @@ pub fn eth_read32<D>(
     while flags == 0 {
         flags = read32(user_data, cmd_addr + 12)?;
         if start_time.elapsed() > timeout {
-            return Err(LuwenError::Custom(
+            return Err(NocError::Timeout(
                 "Ethernet timeout while waiting for flags to come back".to_string(),
-            ));
+            )
+            .into());
         }
     }
 
@@ -271,9 +274,10 @@ pub fn block_read<D>(
         while curr_wptr == curr_rptr {
             curr_wptr = read32(user_data, command_q_addr + RESP_Q_ADDR + 4 * WR_PTR_OFFSET)?;
             if start_time.elapsed() > timeout {
-                return Err(LuwenError::Custom(
+                return Err(NocError::Timeout(
                     "Ethernet timeout while waiting for read queue to be cleared".to_string(),
-                ));
+                )
+                .into());
             }
         }
 
@@ -287,9 +291,10 @@ pub fn block_read<D>(
         while flags == 0 {
             flags = read32(user_data, cmd_addr + 12)?;
             if start_time.elapsed() > timeout {
-                return Err(LuwenError::Custom(
+                return Err(NocError::Timeout(
                     "Ethernet timeout while waiting for flags to come back".to_string(),
-                ));
+                )
+                .into());
             }
         }
 
@@ -454,6 +459,42 @@ pub fn block_write<D>(
     Ok(())
 }
 
+/// The size of the ethernet core L1, the command queue has to fit inside of it.
+const ETH_L1_SIZE: u32 = 256 * 1024;
+
+/// Check that `command_q_addr` points at something that looks like the ethernet command queues.
+/// The queues don't have a signature, so this checks that the address is a sane L1 address
+/// and that the read and write pointers of the host queues are in range.
+pub fn validate_command_queue<D>(
+    user_data: &mut D,
+    mut read32: impl FnMut(&mut D, u32) -> Result<u32, PciError>,
+    command_q_addr: u32,
+) -> Result<(), LuwenError> {
+    let queues_end = command_q_addr as u64 + (REQ_Q_ADDR + Q_NAME.len() as u32 * Q_SIZE) as u64;
+    if command_q_addr == 0 || command_q_addr & 0x3 != 0 || queues_end > ETH_L1_SIZE as u64 {
+        return Err(LuwenError::Custom(format!(
+            "Ethernet command queue address {command_q_addr:#x} is not a valid L1 address, the ethernet firmware may use a different queue layout"
+        )));
+    }
+
+    // Only the request and response queues are shared with the host.
+    for i in [0, 2] {
+        let name = Q_NAME[i as usize];
+        let queue_addr = command_q_addr + REQ_Q_ADDR + 4 * (i * Q_SIZE_WORDS);
+        let wr_ptr = read32(user_data, queue_addr + 4 * WR_PTR_OFFSET)?;
+        let rd_ptr = read32(user_data, queue_addr + 4 * RD_PTR_OFFSET)?;
+
+        // The pointers wrap at twice the buffer size so that a full queue can be told apart from an empty one.
+        if wr_ptr >= 2 * CMD_BUF_SIZE || rd_ptr >= 2 * CMD_BUF_SIZE {
+            return Err(LuwenError::Custom(format!(
+                "Ethernet command queue at {command_q_addr:#x} does not match the expected layout, {name} has wr_ptr {wr_ptr} and rd_ptr {rd_ptr}"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 pub fn fixup_queues<D>(
     user_data: &mut D,
     mut read32: impl FnMut(&mut D, u32) -> Result<u32, PciError>,
@@ -543,3 +584,68 @@ pub fn print_queue_state<D>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    const COMMAND_Q_ADDR: u32 = 0x11080;
+
+    /// L1 with the request and response queue pointers of the command queue at `command_q_addr` set.
+    fn l1(command_q_addr: u32, pointers: [(u32, u32); 2]) -> HashMap<u32, u32> {
+        let mut l1 = HashMap::new();
+        for (i, (wr_ptr, rd_ptr)) in [0, 2].into_iter().zip(pointers) {
+            let queue_addr = command_q_addr + REQ_Q_ADDR + 4 * (i * Q_SIZE_WORDS);
+            l1.insert(queue_addr + 4 * WR_PTR_OFFSET, wr_ptr);
+            l1.insert(queue_addr + 4 * RD_PTR_OFFSET, rd_ptr);
+        }
+        l1
+    }
+
+    fn validate(l1: &mut HashMap<u32, u32>, command_q_addr: u32) -> Result<(), LuwenError> {
+        validate_command_queue(
+            l1,
+            |l1, addr| Ok(l1.get(&addr).copied().unwrap_or(0)),
+            command_q_addr,
+        )
+    }
+
+    #[test]
+    fn accepts_expected_layout() {
+        // A full queue has the write pointer one buffer ahead of the read pointer.
+        let mut l1 = l1(COMMAND_Q_ADDR, [(7, 3), (2, 2)]);
+        validate(&mut l1, COMMAND_Q_ADDR).unwrap();
+    }
+
+    #[test]
+    fn rejects_bad_addresses() {
+        let mut l1 = HashMap::new();
+
+        // Zero, misaligned, and a queue that would run past the end of L1.
+        for addr in [0, COMMAND_Q_ADDR + 2, ETH_L1_SIZE - REQ_Q_ADDR] {
+            let err = validate(&mut l1, addr).unwrap_err();
+            assert!(
+                err.to_string().contains("is not a valid L1 address"),
+                "{addr:#x}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_pointers_out_of_range() {
+        for pointers in [
+            [(2 * CMD_BUF_SIZE, 0), (0, 0)],
+            [(0, 0), (0, 2 * CMD_BUF_SIZE)],
+        ] {
+            let mut l1 = l1(COMMAND_Q_ADDR, pointers);
+            let err = validate(&mut l1, COMMAND_Q_ADDR).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("does not match the expected layout"),
+                "{pointers:?}: {err}"
+            );
+        }
+    }
+}
@@ -59,6 +59,11 @@ impl From<DeviceInfo> for luwen_if::DeviceInfo {
             device_id: value.device_id,
             bar_size: value.bar_size,
             board_id: value.board_id,
+            pcie_current_link_width: 0,
+            pcie_current_link_gen: 0,
+            pcie_max_link_width: 0,
+            pcie_max_link_gen: 0,
+            driver_version: 0,
         }
     }
 }
@@ -164,6 +169,21 @@ pub fn callback_glue(
                 (glue_data.axi_write)(addr, data, len, glue_data.user_data);
                 Ok(())
             }
+            // The c callbacks are expected to be used from a single thread,
+            // so the read and the writeback can't be interleaved with another luwen access.
+            luwen_if::FnAxi::Rmw32 {
+                addr,
+                mask,
+                value,
+                result,
+            } => {
+                let mut current = [0u8; 4];
+                (glue_data.axi_read)(addr, current.as_mut_ptr(), 4, glue_data.user_data);
+                let value = (u32::from_le_bytes(current) & !mask) | (value & mask);
+                (glue_data.axi_write)(addr, value.to_le_bytes().as_ptr(), 4, glue_data.user_data);
+                unsafe { *result = value };
+                Ok(())
+            }
         },
         FnOptions::Noc(op) => match op {
             luwen_if::FnNoc::Read {
@@ -188,12 +208,50 @@ pub fn callback_glue(
                 (glue_data.noc_write)(noc_id, x, y, addr, data, len, glue_data.user_data);
                 Ok(())
             }
+            luwen_if::FnNoc::Rmw32 {
+                noc_id,
+                x,
+                y,
+                addr,
+                mask,
+                value,
+                result,
+            } => {
+                let mut current = [0u8; 4];
+                (glue_data.noc_read)(
+                    noc_id,
+                    x,
+                    y,
+                    addr,
+                    current.as_mut_ptr(),
+                    4,
+                    glue_data.user_data,
+                );
+                let value = (u32::from_le_bytes(current) & !mask) | (value & mask);
+                (glue_data.noc_write)(
+                    noc_id,
+                    x,
+                    y,
+                    addr,
+                    value.to_le_bytes().as_ptr(),
+                    4,
+                    glue_data.user_data,
+                );
+                unsafe { *result = value };
+                Ok(())
+            }
             luwen_if::FnNoc::Broadcast {
                 noc_id,
                 addr,
                 data,
                 len,
+                config,
             } => {
+                if config != luwen_if::BroadcastConfig::default() {
+                    return Err(
+                        "Broadcast configuration is not supported by the C interface".into(),
+                    );
+                }
                 (glue_data.noc_broadcast)(noc_id, addr, data, len, glue_data.user_data);
                 Ok(())
             }
@@ -249,12 +307,58 @@ pub fn callback_glue(
                 );
                 Ok(())
             }
+            luwen_if::FnNoc::Rmw32 {
+                noc_id,
+                x,
+                y,
+                addr,
+                mask,
+                value,
+                result,
+            } => {
+                let eth_addr = || EthAddr {
+                    shelf_x: op.addr.shelf_x,
+                    shelf_y: op.addr.shelf_y,
+                    rack_x: op.addr.rack_x,
+                    rack_y: op.addr.rack_y,
+                };
+                let mut current = [0u8; 4];
+                (glue_data.eth_read)(
+                    eth_addr(),
+                    noc_id,
+                    x,
+                    y,
+                    addr,
+                    current.as_mut_ptr(),
+                    4,
+                    glue_data.user_data,
+                );
+                let value = (u32::from_le_bytes(current) & !mask) | (value & mask);
+                (glue_data.eth_write)(
+                    eth_addr(),
+                    noc_id,
+                    x,
+                    y,
+                    addr,
+                    value.to_le_bytes().as_ptr(),
+                    4,
+                    glue_data.user_data,
+                );
+                unsafe { *result = value };
+                Ok(())
+            }
             luwen_if::FnNoc::Broadcast {
                 noc_id,
                 addr,
                 data,
                 len,
+                config,
             } => {
+                if config != luwen_if::BroadcastConfig::default() {
+                    return Err(
+                        "Broadcast configuration is not supported by the C interface".into(),
+                    );
+                }
                 (glue_data.eth_broadcast)(
                     EthAddr {
                         shelf_x: op.addr.shelf_x,
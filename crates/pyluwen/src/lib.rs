@@ -6,16 +6,20 @@ use std::str::FromStr;
 
 use luwen_core::Arch;
 use luwen_if::chip::{
-    wait_for_init, ArcMsg, ArcMsgOk, ArcMsgOptions, ChipImpl, HlComms, HlCommsInterface, InitError,
-    NocInterface,
+    wait_for_init, ArcMsg, ArcMsgOk, ArcMsgOptions, ChipImpl, FailureTolerance, HlComms,
+    HlCommsInterface, InitError, NocInterface,
+};
+use luwen_if::{
+    ArcState, CallbackStorage, ChipDetectOptions, DeviceInfo, FwType, PowerState, TypedArcMsg,
+    UninitChip,
 };
-use luwen_if::{CallbackStorage, ChipDetectOptions, DeviceInfo, UninitChip};
 use luwen_ref::{DmaConfig, ExtendedPciDeviceWrapper};
 use pyo3::exceptions::PyException;
 use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyList, PyType};
 
 #[pyclass]
-pub struct PciChip(luwen_if::chip::Chip);
+pub struct PciChip(luwen_if::chip::Chip, LazyChipIdentity);
 
 impl Deref for PciChip {
     type Target = luwen_if::chip::Chip;
@@ -32,7 +36,7 @@ impl DerefMut for PciChip {
 }
 
 #[pyclass]
-pub struct PciWormhole(luwen_if::chip::Wormhole);
+pub struct PciWormhole(luwen_if::chip::Wormhole, LazyChipIdentity);
 
 impl Deref for PciWormhole {
     type Target = luwen_if::chip::Wormhole;
@@ -49,7 +53,7 @@ impl DerefMut for PciWormhole {
 }
 
 #[pyclass]
-pub struct PciGrayskull(luwen_if::chip::Grayskull);
+pub struct PciGrayskull(luwen_if::chip::Grayskull, LazyChipIdentity);
 
 impl Deref for PciGrayskull {
     type Target = luwen_if::chip::Grayskull;
@@ -66,7 +70,7 @@ impl DerefMut for PciGrayskull {
 }
 
 #[pyclass]
-pub struct PciBlackhole(luwen_if::chip::Blackhole);
+pub struct PciBlackhole(luwen_if::chip::Blackhole, LazyChipIdentity);
 
 impl Deref for PciBlackhole {
     type Target = luwen_if::chip::Blackhole;
@@ -96,6 +100,81 @@ impl DmaBuffer {
     }
 }
 
+/// A tlb window that stays programmed between accesses.
+/// Offsets passed to read and write are relative to the noc address the window was bound to.
+#[pyclass]
+pub struct TlbWindow {
+    device: ExtendedPciDeviceWrapper,
+    #[pyo3(get)]
+    index: u32,
+    tlb: ttkmd_if::Tlb,
+    bar_addr: u64,
+    #[pyo3(get)]
+    size: u64,
+}
+
+impl TlbWindow {
+    fn check_range(&self, offset: u64, len: usize) -> PyResult<u32> {
+        match offset.checked_add(len as u64) {
+            Some(end) if end <= self.size => Ok((self.bar_addr + offset) as u32),
+            _ => Err(PyException::new_err(format!(
+                "Access of {len} bytes at offset {offset:#x} is outside of tlb {} ({:#x} bytes)",
+                self.index, self.size
+            ))),
+        }
+    }
+}
+
+#[pymethods]
+impl TlbWindow {
+    /// The noc address that offset 0 of the window maps to.
+    #[getter]
+    pub fn addr(&self) -> u64 {
+        self.tlb.local_offset
+    }
+
+    pub fn read(&self, offset: u64, data: pyo3::buffer::PyBuffer<u8>) -> PyResult<()> {
+        check_buffer(&data, true)?;
+        let bar_addr = self.check_range(offset, data.len_bytes())?;
+
+        let ptr: *mut u8 = data.buf_ptr().cast();
+        let data = unsafe { std::slice::from_raw_parts_mut(ptr, data.len_bytes()) };
+        self.device
+            .borrow_mut()
+            .read_block(bar_addr, data)
+            .map_err(|v| PyException::new_err(v.to_string()))
+    }
+
+    pub fn write(&self, offset: u64, data: pyo3::buffer::PyBuffer<u8>) -> PyResult<()> {
+        check_buffer(&data, false)?;
+        let bar_addr = self.check_range(offset, data.len_bytes())?;
+
+        let ptr: *const u8 = data.buf_ptr().cast();
+        let data = unsafe { std::slice::from_raw_parts(ptr, data.len_bytes()) };
+        self.device
+            .borrow_mut()
+            .write_block(bar_addr, data)
+            .map_err(|v| PyException::new_err(v.to_string()))
+    }
+
+    /// Move the window to a new noc address on the same core.
+    pub fn rebind(&mut self, addr: u64) -> PyResult<()> {
+        let mut tlb = self.tlb.clone();
+        tlb.local_offset = addr;
+        let (bar_addr, size) = self
+            .device
+            .borrow_mut()
+            .setup_tlb(self.index, tlb.clone())
+            .map_err(|v| PyException::new_err(v.to_string()))?;
+
+        self.tlb = tlb;
+        self.bar_addr = bar_addr;
+        self.size = size;
+
+        Ok(())
+    }
+}
+
 #[pyclass]
 pub struct NeighbouringChip {
     #[pyo3(get)]
@@ -117,6 +196,7 @@ impl From<luwen_if::chip::NeighbouringChip> for NeighbouringChip {
 }
 
 #[pyclass]
+#[derive(serde::Serialize)]
 pub struct Telemetry {
     #[pyo3(get)]
     board_id: u64,
@@ -232,6 +312,10 @@ pub struct Telemetry {
     tt_flash_version: u32,
     #[pyo3(get)]
     timer_heartbeat: u32,
+    #[serde(skip)]
+    gddr_channels_trained: Vec<bool>,
+    #[serde(skip)]
+    gddr_channel_temps: Vec<u32>,
 }
 impl From<luwen_if::chip::Telemetry> for Telemetry {
     fn from(value: luwen_if::chip::Telemetry) -> Self {
@@ -293,6 +377,133 @@ impl From<luwen_if::chip::Telemetry> for Telemetry {
             tt_flash_version: value.tt_flash_version,
             fw_bundle_version: value.fw_bundle_version,
             timer_heartbeat: value.timer_heartbeat,
+            gddr_channels_trained: (0..value.gddr_channel_count())
+                .map(|channel| value.gddr_channel_trained(channel))
+                .collect(),
+            gddr_channel_temps: value.gddr_temps(),
+        }
+    }
+}
+
+impl Telemetry {
+    fn bundle_telemetry(&self) -> luwen_if::chip::Telemetry {
+        luwen_if::chip::Telemetry {
+            fw_bundle_version: self.fw_bundle_version,
+            ..Default::default()
+        }
+    }
+}
+
+#[pymethods]
+impl Telemetry {
+    /// Return every telemetry field as a dict keyed by field name.
+    pub fn to_dict(&self, py: Python) -> PyResult<Py<PyDict>> {
+        let value = serde_json::to_value(self).map_err(|v| PyException::new_err(v.to_string()))?;
+
+        let dict = PyDict::new(py);
+        if let serde_json::Value::Object(fields) = value {
+            for (name, value) in fields {
+                match value {
+                    serde_json::Value::Number(value) => dict.set_item(name, value.as_u64())?,
+                    _ => dict.set_item(name, py.None())?,
+                }
+            }
+        }
+
+        Ok(dict.into())
+    }
+
+    /// Serialize every telemetry field as a json object.
+    pub fn as_json(&self) -> PyResult<String> {
+        serde_json::to_string(self).map_err(|v| PyException::new_err(v.to_string()))
+    }
+
+    /// Return the firmware bundle version in MAJOR.MINOR.PATCH.BUILD format.
+    pub fn fw_bundle_version_str(&self) -> String {
+        self.bundle_telemetry().fw_bundle_version_str()
+    }
+
+    /// Return whether the firmware bundle has the feature flag, one of fw_features().
+    pub fn fw_feature(&self, flag: &str) -> PyResult<bool> {
+        self.bundle_telemetry().fw_feature(flag).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Unknown firmware feature {flag:?}, expected one of {:?}",
+                Self::fw_features()
+            ))
+        })
+    }
+
+    /// The names of the firmware features that can be checked with fw_feature.
+    #[staticmethod]
+    pub fn fw_features() -> Vec<&'static str> {
+        luwen_if::chip::FW_FEATURES
+            .iter()
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Return the board information or None if the board type is unknown.
+    pub fn board_info(&self) -> Option<BoardInfo> {
+        let serial_num = ((self.board_id_high as u64) << 32) | self.board_id_low as u64;
+        luwen_if::chip::BoardInfo::from_serial_number(serial_num).map(|v| v.into())
+    }
+
+    /// Return true if gddr channel `channel` has finished training without an error.
+    pub fn gddr_channel_trained(&self, channel: usize) -> bool {
+        self.gddr_channels_trained
+            .get(channel)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Return the training temperature of each gddr channel, only reported on wormhole.
+    pub fn gddr_temps(&self) -> Vec<u32> {
+        self.gddr_channel_temps.clone()
+    }
+}
+
+#[pyclass]
+pub struct BoardInfo {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    arch: String,
+    #[pyo3(get)]
+    asic_count: u8,
+    #[pyo3(get)]
+    is_galaxy: bool,
+    #[pyo3(get)]
+    has_ethernet: bool,
+}
+
+impl From<luwen_if::chip::BoardInfo> for BoardInfo {
+    fn from(value: luwen_if::chip::BoardInfo) -> Self {
+        Self {
+            name: value.name.to_string(),
+            arch: value.arch.to_string(),
+            asic_count: value.asic_count,
+            is_galaxy: value.is_galaxy,
+            has_ethernet: value.has_ethernet,
+        }
+    }
+}
+
+#[pyclass]
+pub struct ChipHealth {
+    #[pyo3(get)]
+    arc_responsive: bool,
+    #[pyo3(get)]
+    noc_responsive: bool,
+    #[pyo3(get)]
+    eth_responsive: Option<bool>,
+}
+
+impl From<luwen_if::chip::ChipHealth> for ChipHealth {
+    fn from(value: luwen_if::chip::ChipHealth) -> Self {
+        Self {
+            arc_responsive: value.arc_responsive,
+            noc_responsive: value.noc_responsive,
+            eth_responsive: value.eth_responsive,
         }
     }
 }
@@ -314,10 +525,531 @@ impl From<luwen_if::chip::AxiData> for AxiData {
     }
 }
 
+/// The dma support of a chip and host, see config_dma.
+#[pyclass]
+pub struct DmaCaps {
+    /// Physical address bits usable for dma, 0 if unknown.
+    /// When this is 32 or less, config_dma should be called without a dma_64_bit_addr.
+    #[pyo3(get)]
+    max_address_bits: u32,
+    #[pyo3(get)]
+    max_dma_buf_size_log2: u32,
+    #[pyo3(get)]
+    msi: bool,
+}
+
+impl From<ttkmd_if::DmaCapabilities> for DmaCaps {
+    fn from(value: ttkmd_if::DmaCapabilities) -> Self {
+        Self {
+            max_address_bits: value.max_address_bits,
+            max_dma_buf_size_log2: value.max_dma_buf_size_log2,
+            msi: value.msi,
+        }
+    }
+}
+
+const TYPED_ARC_MSG_NAMES: &[&str] = &[
+    "nop",
+    "test",
+    "arc_go_to_sleep",
+    "set_power_state_busy",
+    "set_power_state_short_idle",
+    "set_power_state_long_idle",
+    "fw_version_arc_l2",
+    "fw_version_fw_bundle",
+    "fw_version_fw_bundle_spi",
+    "get_smbus_telemetry_addr",
+    "set_arc_state_a0",
+    "set_arc_state_a1",
+    "set_arc_state_a3",
+    "set_arc_state_a5",
+    "reset_safe_clks",
+    "toggle_tensix_reset",
+    "deassert_riscv_reset",
+    "get_aiclk",
+    "trigger_reset",
+    "get_harvesting",
+    "trigger_spi_copy_ltor",
+    "get_spi_dump_addr",
+    "spi_read",
+    "spi_write",
+];
+
+/// Map a message name onto the matching TypedArcMsg.
+/// Messages which take an argument build it from args as (arg0 | arg1 << 16).
+fn typed_arc_msg(name: &str, args: &[u16]) -> PyResult<TypedArcMsg> {
+    let arg = args.first().copied().unwrap_or(0) as u32
+        | (args.get(1).copied().unwrap_or(0) as u32) << 16;
+
+    let msg = match name {
+        "nop" => TypedArcMsg::Nop,
+        "test" => TypedArcMsg::Test { arg },
+        "arc_go_to_sleep" => TypedArcMsg::ArcGoToSleep,
+        "set_power_state_busy" => TypedArcMsg::SetPowerState(PowerState::Busy),
+        "set_power_state_short_idle" => TypedArcMsg::SetPowerState(PowerState::ShortIdle),
+        "set_power_state_long_idle" => TypedArcMsg::SetPowerState(PowerState::LongIdle),
+        "fw_version_arc_l2" => TypedArcMsg::FwVersion(FwType::ArcL2),
+        "fw_version_fw_bundle" => TypedArcMsg::FwVersion(FwType::FwBundle),
+        "fw_version_fw_bundle_spi" => TypedArcMsg::FwVersion(FwType::FwBundleSPI),
+        "get_smbus_telemetry_addr" => TypedArcMsg::GetSmbusTelemetryAddr,
+        "set_arc_state_a0" => TypedArcMsg::SetArcState {
+            state: ArcState::A0,
+        },
+        "set_arc_state_a1" => TypedArcMsg::SetArcState {
+            state: ArcState::A1,
+        },
+        "set_arc_state_a3" => TypedArcMsg::SetArcState {
+            state: ArcState::A3,
+        },
+        "set_arc_state_a5" => TypedArcMsg::SetArcState {
+            state: ArcState::A5,
+        },
+        "reset_safe_clks" => TypedArcMsg::ResetSafeClks { arg },
+        "toggle_tensix_reset" => TypedArcMsg::ToggleTensixReset { arg },
+        "deassert_riscv_reset" => TypedArcMsg::DeassertRiscVReset,
+        "get_aiclk" => TypedArcMsg::GetAiclk,
+        "trigger_reset" => TypedArcMsg::TriggerReset,
+        "get_harvesting" => TypedArcMsg::GetHarvesting,
+        "trigger_spi_copy_ltor" => TypedArcMsg::TriggerSpiCopyLtoR,
+        "get_spi_dump_addr" => TypedArcMsg::GetSpiDumpAddr,
+        "spi_read" => TypedArcMsg::SpiRead { addr: arg },
+        "spi_write" => TypedArcMsg::SpiWrite,
+        name => {
+            return Err(PyException::new_err(format!(
+                "Unknown arc message {name}; expected one of {}",
+                TYPED_ARC_MSG_NAMES.join(", ")
+            )));
+        }
+    };
+
+    Ok(msg)
+}
+
+/// Convert the ordering passed from python into a tlb ordering supported by `arch`.
+fn parse_ordering(ordering: u8, arch: Arch) -> PyResult<ttkmd_if::tlb::Ordering> {
+    match ttkmd_if::tlb::Ordering::from(ordering) {
+        ttkmd_if::tlb::Ordering::UNKNOWN(ordering) => Err(PyException::new_err(format!(
+            "Invalid ordering {ordering}, expected one of 0 (relaxed), 1 (strict), 2 (posted) or 3 (posted strict)."
+        ))),
+        ttkmd_if::tlb::Ordering::PostedStrict if !arch.is_blackhole() => Err(PyException::new_err(
+            format!("Ordering 3 (posted strict) is not supported on {arch}."),
+        )),
+        ordering => Ok(ordering),
+    }
+}
+
+/// The pci device used for a noc access with an explicit chunk size,
+/// remote chips are rejected because their accesses go over ethernet rather than through a tlb.
+fn chunked_pci_interface(chip: &dyn ChipImpl) -> PyResult<PciInterface<'_>> {
+    PciInterface::from_chip(chip)
+        .ok_or_else(|| PyException::new_err("chunk_size is only supported on pci connected chips"))
+}
+
+/// Check that a python buffer can be safely viewed as a contiguous slice of bytes,
+/// if `writable` is set the buffer must also not be read-only.
+fn check_buffer(data: &pyo3::buffer::PyBuffer<u8>, writable: bool) -> PyResult<()> {
+    if !data.is_c_contiguous() {
+        return Err(PyException::new_err("Buffer must be C contiguous"));
+    }
+    if writable && data.readonly() {
+        return Err(PyException::new_err("Buffer must be writable"));
+    }
+
+    Ok(())
+}
+
+mod exceptions {
+    // The pyo3 exception macro checks a cfg that newer compilers do not know about.
+    #![allow(unexpected_cfgs)]
+
+    use pyo3::exceptions::PyException;
+
+    pyo3::create_exception!(pyluwen, NocError, PyException);
+    pyo3::create_exception!(pyluwen, NocTimeoutError, NocError);
+    pyo3::create_exception!(pyluwen, NocInvalidCoordError, NocError);
+    pyo3::create_exception!(pyluwen, NocTlbUnavailableError, NocError);
+    pyo3::create_exception!(pyluwen, NocHardwareFaultError, NocError);
+    pyo3::create_exception!(
+        pyluwen,
+        DevicePermissionError,
+        pyo3::exceptions::PyPermissionError
+    );
+}
+use exceptions::{
+    DevicePermissionError, NocError, NocHardwareFaultError, NocInvalidCoordError, NocTimeoutError,
+    NocTlbUnavailableError,
+};
+
+/// Convert an error from opening a pci device into a python exception,
+/// a missing permission is raised as DevicePermissionError so that it is not mistaken for a broken device.
+fn open_error(err: ttkmd_if::PciOpenError, pci_interface: usize) -> PyErr {
+    let message = format!("Could not open pci device {pci_interface}: {err}");
+    match err {
+        ttkmd_if::PciOpenError::PermissionDenied { .. } => DevicePermissionError::new_err(message),
+        _ => PyException::new_err(message),
+    }
+}
+
+/// Convert an error from a noc access into a python exception, errors that the backend
+/// reported as a NocError are raised as the matching NocError subclass.
+fn noc_error(err: Box<dyn std::error::Error>, prefix: &str) -> PyErr {
+    let message = format!("{prefix}{err}");
+    match err.downcast_ref::<luwen_if::error::NocError>() {
+        Some(luwen_if::error::NocError::Timeout(_)) => NocTimeoutError::new_err(message),
+        Some(
+            luwen_if::error::NocError::InvalidCoord { .. }
+            | luwen_if::error::NocError::HarvestedCoord { .. },
+        ) => NocInvalidCoordError::new_err(message),
+        Some(luwen_if::error::NocError::TlbUnavailable { .. }) => {
+            NocTlbUnavailableError::new_err(message)
+        }
+        Some(luwen_if::error::NocError::HardwareFault(_)) => {
+            NocHardwareFaultError::new_err(message)
+        }
+        Some(luwen_if::error::NocError::Unsupported(_)) => {
+            pyo3::exceptions::PyNotImplementedError::new_err(message)
+        }
+        None => PyException::new_err(message),
+    }
+}
+
+/// Identifies a physical chip no matter how it was opened: arch, board serial and location on the board.
+/// Wormhole chips are located by their ethernet coordinate so that the pci and remote views of a chip match,
+/// other archs don't have a meaningful coordinate and fall back to the pci interface.
+///
+/// The identity is read the first time a chip is hashed or compared and then cached, handles cloned from
+/// it share the cache. If it can't be read the chip gets a unique identity that only those handles share.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum ChipIdentity {
+    Physical {
+        arch: Arch,
+        serial: u64,
+        coord: Option<luwen_if::EthAddr>,
+        interface: Option<u32>,
+    },
+    Unknown(u64),
+}
+
+fn chip_identity(chip: &dyn ChipImpl) -> ChipIdentity {
+    static NEXT_UNKNOWN: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+    let arch = chip.get_arch();
+    let serial = chip.get_telemetry().ok().map(|v| v.board_serial_number());
+    let (coord, interface) = if arch.is_wormhole() {
+        (chip.get_local_chip_coord().ok(), None)
+    } else {
+        let interface = chip
+            .get_device_info()
+            .ok()
+            .flatten()
+            .map(|v| v.interface_id);
+        (None, interface)
+    };
+
+    match serial {
+        Some(serial) if coord.is_some() || interface.is_some() => ChipIdentity::Physical {
+            arch,
+            serial,
+            coord,
+            interface,
+        },
+        _ => ChipIdentity::Unknown(NEXT_UNKNOWN.fetch_add(1, std::sync::atomic::Ordering::Relaxed)),
+    }
+}
+
+/// The identity of a chip, read from the chip on first use so that opening and casting chips never
+/// touches the hardware.
+#[derive(Clone, Default)]
+struct LazyChipIdentity(std::sync::Arc<std::sync::OnceLock<ChipIdentity>>);
+
+impl LazyChipIdentity {
+    fn get(&self, chip: &dyn ChipImpl) -> &ChipIdentity {
+        self.0.get_or_init(|| chip_identity(chip))
+    }
+}
+
+/// Build the python chip classes from their chip, the identity is left to be read on first use.
+macro_rules! chip_class_from {
+    ($name:ident, $inner:ty) => {
+        impl From<$inner> for $name {
+            fn from(chip: $inner) -> Self {
+                Self(chip, LazyChipIdentity::default())
+            }
+        }
+    };
+}
+
+chip_class_from!(PciChip, luwen_if::chip::Chip);
+chip_class_from!(PciWormhole, luwen_if::chip::Wormhole);
+chip_class_from!(PciGrayskull, luwen_if::chip::Grayskull);
+chip_class_from!(PciBlackhole, luwen_if::chip::Blackhole);
+chip_class_from!(RemoteWormhole, luwen_if::chip::Wormhole);
+
+/// The identity of any of the pyluwen chip classes, None if value is not a chip.
+fn chip_identity_of(value: &PyAny) -> Option<ChipIdentity> {
+    if let Ok(chip) = value.extract::<PyRef<PciChip>>() {
+        Some(chip.1.get(&chip.0).clone())
+    } else if let Ok(chip) = value.extract::<PyRef<PciGrayskull>>() {
+        Some(chip.1.get(&chip.0).clone())
+    } else if let Ok(chip) = value.extract::<PyRef<PciWormhole>>() {
+        Some(chip.1.get(&chip.0).clone())
+    } else if let Ok(chip) = value.extract::<PyRef<RemoteWormhole>>() {
+        Some(chip.1.get(&chip.0).clone())
+    } else if let Ok(chip) = value.extract::<PyRef<PciBlackhole>>() {
+        Some(chip.1.get(&chip.0).clone())
+    } else {
+        None
+    }
+}
+
+/// An axi address given either as a number or as a register name.
+#[derive(FromPyObject)]
+pub enum AxiAddr {
+    Addr(u64),
+    Name(String),
+}
+
+/// Read a register until `(value & mask) == expected` or the timeout expires, returns the last value read
+/// and whether it matched. The GIL is released while waiting between reads.
+fn poll32(
+    py: Python,
+    mask: u32,
+    expected: u32,
+    timeout_secs: f64,
+    poll_interval: f64,
+    mut read: impl FnMut() -> PyResult<u32>,
+) -> PyResult<(u32, bool)> {
+    let timeout = std::time::Duration::try_from_secs_f64(timeout_secs)
+        .map_err(|_| PyException::new_err(format!("Invalid timeout {timeout_secs}")))?;
+    let poll_interval = std::time::Duration::try_from_secs_f64(poll_interval)
+        .map_err(|_| PyException::new_err(format!("Invalid poll interval {poll_interval}")))?;
+
+    let start = std::time::Instant::now();
+    loop {
+        let value = read()?;
+        if value & mask == expected {
+            return Ok((value, true));
+        }
+        if start.elapsed() >= timeout {
+            return Ok((value, false));
+        }
+
+        py.check_signals()?;
+        py.allow_threads(|| std::thread::sleep(poll_interval));
+    }
+}
+
+type SpiResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Size of the chunks used when reading back or checksumming spi.
+const SPI_CHUNK_SIZE: usize = 4096;
+
+/// Write data to spi then read it back, failing with the first offset that does not match.
+fn spi_write_verified(
+    addr: u32,
+    data: &[u8],
+    write: impl Fn(u32, &[u8]) -> SpiResult,
+    read: impl Fn(u32, &mut [u8]) -> SpiResult,
+) -> PyResult<()> {
+    let len = u32::try_from(data.len()).ok();
+    if len.and_then(|len| addr.checked_add(len)).is_none() {
+        return Err(PyException::new_err(format!(
+            "SPI region {addr:#x} + {:#x} is out of range",
+            data.len()
+        )));
+    }
+
+    write(addr, data).map_err(|v| PyException::new_err(v.to_string()))?;
+
+    let mut readback = vec![0u8; SPI_CHUNK_SIZE];
+    for (index, expected) in data.chunks(SPI_CHUNK_SIZE).enumerate() {
+        let offset = index * SPI_CHUNK_SIZE;
+        let readback = &mut readback[..expected.len()];
+        read(addr + offset as u32, readback).map_err(|v| PyException::new_err(v.to_string()))?;
+
+        if let Some(mismatch) = expected
+            .iter()
+            .zip(readback.iter())
+            .position(|(a, b)| a != b)
+        {
+            let offset = offset + mismatch;
+            return Err(PyException::new_err(format!(
+                "SPI verification failed at offset {offset:#x}: wrote {:#04x} but read {:#04x}",
+                expected[mismatch], readback[mismatch]
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Write data to spi in chunks of chunk_size bytes, calling `callback` with the number of bytes written after each chunk.
+/// The GIL is released while a chunk is written and python signals are checked between chunks,
+/// so a KeyboardInterrupt stops the write at a chunk boundary.
+fn spi_write_progress(
+    py: Python,
+    addr: u32,
+    data: &[u8],
+    chunk_size: usize,
+    callback: &PyObject,
+    write: impl Fn(u32, &[u8]) -> SpiResult + Send + Sync,
+) -> PyResult<()> {
+    if chunk_size == 0 {
+        return Err(PyException::new_err("chunk_size must be greater than 0"));
+    }
+    let len = u32::try_from(data.len()).ok();
+    if len.and_then(|len| addr.checked_add(len)).is_none() {
+        return Err(PyException::new_err(format!(
+            "SPI region {addr:#x} + {:#x} is out of range",
+            data.len()
+        )));
+    }
+
+    let mut written = 0;
+    for chunk in data.chunks(chunk_size) {
+        py.allow_threads(|| write(addr + written as u32, chunk).map_err(|v| v.to_string()))
+            .map_err(|v| {
+                PyException::new_err(format!("SPI write failed at offset {written:#x}: {v}"))
+            })?;
+        written += chunk.len();
+
+        callback.call1(py, (written,))?;
+        py.check_signals()?;
+    }
+
+    Ok(())
+}
+
+/// Compute the crc32 (IEEE) of len bytes of spi starting at addr.
+fn spi_crc32(addr: u32, len: u32, read: impl Fn(u32, &mut [u8]) -> SpiResult) -> PyResult<u32> {
+    if addr.checked_add(len).is_none() {
+        return Err(PyException::new_err(format!(
+            "SPI region {addr:#x} + {len:#x} is out of range"
+        )));
+    }
+
+    let mut crc = 0xffffffffu32;
+
+    let mut buffer = vec![0u8; SPI_CHUNK_SIZE];
+    let mut offset = 0;
+    while offset < len {
+        let chunk = &mut buffer[..(len - offset).min(SPI_CHUNK_SIZE as u32) as usize];
+        read(addr + offset, chunk).map_err(|v| PyException::new_err(v.to_string()))?;
+        crc = luwen_if::chip::crc32_update(crc, chunk);
+
+        offset += chunk.len() as u32;
+    }
+
+    Ok(!crc)
+}
+
+/// Read len bytes of spi starting at addr into the file at path, returns the number of bytes written.
+/// The data is written to `path.tmp` and only renamed to path once it is complete and synced,
+/// so a failed or interrupted dump never leaves a truncated file at path.
+/// The GIL is released while each chunk is read and python signals are checked between chunks.
+fn spi_dump_to_file(
+    py: Python,
+    addr: u32,
+    len: u32,
+    path: &str,
+    read: impl Fn(u32, &mut [u8]) -> SpiResult + Send + Sync,
+) -> PyResult<u32> {
+    use std::io::Write;
+
+    if addr.checked_add(len).is_none() {
+        return Err(PyException::new_err(format!(
+            "SPI region {addr:#x} + {len:#x} is out of range"
+        )));
+    }
+
+    let tmp_path = format!("{path}.tmp");
+    let mut file = std::fs::File::create(&tmp_path)
+        .map_err(|v| PyException::new_err(format!("Could not create {tmp_path}: {v}")))?;
+
+    let mut dump = || -> PyResult<u32> {
+        let mut buffer = vec![0u8; SPI_CHUNK_SIZE];
+        let mut offset = 0;
+        while offset < len {
+            let chunk = &mut buffer[..(len - offset).min(SPI_CHUNK_SIZE as u32) as usize];
+            py.allow_threads(|| read(addr + offset, chunk).map_err(|v| v.to_string()))
+                .map_err(|v| {
+                    PyException::new_err(format!("SPI read failed at offset {offset:#x}: {v}"))
+                })?;
+            file.write_all(chunk)
+                .map_err(|v| PyException::new_err(format!("Could not write to {tmp_path}: {v}")))?;
+
+            offset += chunk.len() as u32;
+            py.check_signals()?;
+        }
+
+        file.sync_all()
+            .map_err(|v| PyException::new_err(format!("Could not sync {tmp_path}: {v}")))?;
+        std::fs::rename(&tmp_path, path).map_err(|v| {
+            PyException::new_err(format!("Could not rename {tmp_path} to {path}: {v}"))
+        })?;
+
+        Ok(offset)
+    };
+
+    let result = dump();
+    if result.is_err() {
+        // Also covers a KeyboardInterrupt raised by check_signals, the partial dump is not useful.
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+
+    result
+}
+
 macro_rules! common_chip_comms_impls {
     ($name:ty) => {
         #[pymethods]
         impl $name {
+            /// Return a new handle to the same chip, for use from another thread.
+            /// Every noc/axi access holds the device lock for its whole tlb setup and transfer,
+            /// so handles to the same chip can be used concurrently.
+            pub fn clone_handle(&self) -> Self {
+                Self(self.0.clone(), self.1.clone())
+            }
+
+            /// The api version of the kernel driver used to open this chip, useful when debugging tlb setup failures.
+            /// Remote chips don't have a pci device of their own, so this raises an exception for them.
+            pub fn driver_api_version(&self) -> PyResult<u32> {
+                match self.0.get_device_info() {
+                    Ok(Some(info)) => Ok(info.driver_version),
+                    Ok(None) => Err(PyException::new_err(
+                        "Only pci connected chips have a driver version",
+                    )),
+                    Err(err) => Err(PyException::new_err(format!(
+                        "Could not get device info: {err}"
+                    ))),
+                }
+            }
+
+            /// Chips are equal if they are the same physical chip, see chip_identity.
+            fn __richcmp__(&self, py: Python, other: &PyAny, op: pyo3::pyclass::CompareOp) -> PyObject {
+                let Some(other) = chip_identity_of(other) else {
+                    return py.NotImplemented();
+                };
+
+                let identity = self.1.get(&self.0);
+                match op {
+                    pyo3::pyclass::CompareOp::Eq => (*identity == other).into_py(py),
+                    pyo3::pyclass::CompareOp::Ne => (*identity != other).into_py(py),
+                    _ => py.NotImplemented(),
+                }
+            }
+
+            fn __hash__(&self) -> u64 {
+                use std::hash::{Hash, Hasher};
+
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                self.1.get(&self.0).hash(&mut hasher);
+                hasher.finish()
+            }
+
+            /// If chunk_size is set the tlb is reprogrammed at least every chunk_size bytes,
+            /// this is only supported on pci connected chips.
+            #[pyo3(signature = (noc_id, x, y, addr, data, chunk_size = None))]
             pub fn noc_read(
                 &self,
                 noc_id: u8,
@@ -325,27 +1057,124 @@ macro_rules! common_chip_comms_impls {
                 y: u8,
                 addr: u64,
                 data: pyo3::buffer::PyBuffer<u8>,
+                chunk_size: Option<u64>,
             ) -> PyResult<()> {
+                check_buffer(&data, true)?;
                 Python::with_gil(|_py| {
                     let ptr: *mut u8 = data.buf_ptr().cast();
                     let len = data.len_bytes();
 
                     let data = unsafe { std::slice::from_raw_parts_mut(ptr, len) };
+                    match chunk_size {
+                        None => self.0.noc_read(noc_id, x, y, addr, data),
+                        Some(chunk_size) => chunked_pci_interface(&self.0)?
+                            .pci_interface
+                            .borrow_mut()
+                            .noc_read_coord(noc_id, x as u32, y as u32, addr, data, Some(chunk_size))
+                            .map_err(luwen_ref::noc_access_error),
+                    }
+                    .map_err(|v| noc_error(v, ""))
+                })
+            }
+
+            /// Read len bytes into a newly allocated bytes object.
+            pub fn noc_read_bytes(
+                &self,
+                py: Python,
+                noc_id: u8,
+                x: u8,
+                y: u8,
+                addr: u64,
+                len: usize,
+            ) -> PyResult<Py<PyBytes>> {
+                let bytes = PyBytes::new_with(py, len, |data| {
                     self.0
                         .noc_read(noc_id, x, y, addr, data)
-                        .map_err(|v| PyException::new_err(v.to_string()))
-                })
+                        .map_err(|v| noc_error(v, ""))
+                })?;
+
+                Ok(bytes.into())
+            }
+
+            /// Read len bytes in chunks of chunk bytes, calling callback(bytes_done, chunk) after each chunk.
+            /// The GIL is released while a chunk is read and python signals are checked between chunks,
+            /// so large regions can be dumped without holding them in memory all at once.
+            #[allow(clippy::too_many_arguments)]
+            pub fn noc_read_stream(
+                &self,
+                py: Python,
+                noc_id: u8,
+                x: u8,
+                y: u8,
+                addr: u64,
+                len: u64,
+                chunk: usize,
+                callback: PyObject,
+            ) -> PyResult<()> {
+                if chunk == 0 {
+                    return Err(PyException::new_err("chunk must be greater than 0"));
+                }
+                if addr.checked_add(len).is_none() {
+                    return Err(PyException::new_err(format!(
+                        "noc_read_stream of {len} bytes at {addr:#x} is outside of the noc address space"
+                    )));
+                }
+
+                let mut buffer = vec![0u8; (chunk as u64).min(len) as usize];
+                let mut done = 0;
+                while done < len {
+                    let data = &mut buffer[..(len - done).min(chunk as u64) as usize];
+                    py.allow_threads(|| {
+                        self.0.noc_read(noc_id, x, y, addr + done, data).map_err(|v| {
+                            noc_error(v, &format!("noc_read_stream failed at offset {done:#x}: "))
+                        })
+                    })?;
+                    done += data.len() as u64;
+
+                    callback.call1(py, (done, PyBytes::new(py, data)))?;
+                    py.check_signals()?;
+                }
+
+                Ok(())
+            }
+
+            /// Return the (x, y) reported by the noc node id register of the core at (x, y).
+            pub fn read_noc_node_id(&self, noc_id: u8, x: u8, y: u8) -> PyResult<(u8, u8)> {
+                self.0
+                    .read_noc_node_id(noc_id, x, y)
+                    .map_err(|v| noc_error(v, "Could not read noc node id: "))
+            }
+
+            /// Read the 4 bytes at addr in device order, without interpreting them as an integer.
+            pub fn noc_read32_raw(
+                &self,
+                py: Python,
+                noc_id: u8,
+                x: u8,
+                y: u8,
+                addr: u64,
+            ) -> PyResult<Py<PyBytes>> {
+                let data = self
+                    .0
+                    .noc_read32_raw(noc_id, x, y, addr)
+                    .map_err(|v| noc_error(v, ""))?;
+
+                Ok(PyBytes::new(py, &data).into())
             }
 
+            /// Read a 32 bit integer, the device is little endian so this is independent of the host byte order.
             pub fn noc_read32(&self, noc_id: u8, x: u8, y: u8, addr: u64) -> PyResult<u32> {
                 let mut data = [0u8; 4];
                 self.0
                     .noc_read(noc_id, x, y, addr, &mut data)
-                    .map_err(|v| PyException::new_err(v.to_string()))?;
+                    .map_err(|v| noc_error(v, ""))?;
 
                 Ok(u32::from_le_bytes(data))
             }
 
+            /// If chunk_size is set the tlb is reprogrammed at least every chunk_size bytes,
+            /// this is only supported on pci connected chips.
+            #[pyo3(signature = (noc_id, x, y, addr, data, chunk_size = None))]
             pub fn noc_write(
                 &self,
                 noc_id: u8,
@@ -353,52 +1182,269 @@ macro_rules! common_chip_comms_impls {
                 y: u8,
                 addr: u64,
                 data: pyo3::buffer::PyBuffer<u8>,
+                chunk_size: Option<u64>,
             ) -> PyResult<()> {
+                check_buffer(&data, false)?;
                 Python::with_gil(|_py| {
                     let ptr: *mut u8 = data.buf_ptr().cast();
                     let len = data.len_bytes();
 
                     let data = unsafe { std::slice::from_raw_parts(ptr, len) };
-                    self.0
-                        .noc_write(noc_id, x, y, addr, data)
-                        .map_err(|v| PyException::new_err(v.to_string()))
+                    match chunk_size {
+                        None => self.0.noc_write(noc_id, x, y, addr, data),
+                        Some(chunk_size) => chunked_pci_interface(&self.0)?
+                            .pci_interface
+                            .borrow_mut()
+                            .noc_write_coord(noc_id, x as u32, y as u32, addr, data, Some(chunk_size))
+                            .map_err(luwen_ref::noc_access_error),
+                    }
+                    .map_err(|v| noc_error(v, ""))
                 })
             }
 
-            pub fn noc_write32(
+            /// Write exactly 4 bytes to addr in device order.
+            pub fn noc_write32_raw(
                 &self,
                 noc_id: u8,
                 x: u8,
                 y: u8,
                 addr: u64,
-                data: u32,
+                data: &[u8],
             ) -> PyResult<()> {
+                let data: [u8; 4] = data.try_into().map_err(|_| {
+                    PyException::new_err(format!(
+                        "noc_write32_raw expects 4 bytes, got {}",
+                        data.len()
+                    ))
+                })?;
                 self.0
-                    .noc_write(noc_id, x, y, addr, &data.to_le_bytes())
-                    .map_err(|v| PyException::new_err(v.to_string()))
+                    .noc_write32_raw(noc_id, x, y, addr, data)
+                    .map_err(|v| noc_error(v, ""))
             }
 
-            pub fn noc_broadcast(
+            /// Write a 32 bit integer as little endian, the device byte order.
+            pub fn noc_write32(
                 &self,
                 noc_id: u8,
-                addr: u64,
+                x: u8,
+                y: u8,
+                addr: u64,
+                data: u32,
+            ) -> PyResult<()> {
+                self.0
+                    .noc_write(noc_id, x, y, addr, &data.to_le_bytes())
+                    .map_err(|v| noc_error(v, ""))
+            }
+
+            pub fn noc_read64(&self, noc_id: u8, x: u8, y: u8, addr: u64) -> PyResult<u64> {
+                self.0
+                    .noc_read64(noc_id, x, y, addr)
+                    .map_err(|v| noc_error(v, ""))
+            }
+
+            pub fn noc_write64(
+                &self,
+                noc_id: u8,
+                x: u8,
+                y: u8,
+                addr: u64,
+                data: u64,
+            ) -> PyResult<()> {
+                self.0
+                    .noc_write64(noc_id, x, y, addr, data)
+                    .map_err(|v| noc_error(v, ""))
+            }
+
+            /// Same as noc_read but (x, y) is checked against the noc grid before any access is made.
+            /// If check_harvesting is set then harvested tensix cores are also rejected.
+            #[pyo3(signature = (noc_id, x, y, addr, data, check_harvesting = false))]
+            pub fn noc_read_checked(
+                &self,
+                noc_id: u8,
+                x: u8,
+                y: u8,
+                addr: u64,
+                data: pyo3::buffer::PyBuffer<u8>,
+                check_harvesting: bool,
+            ) -> PyResult<()> {
+                check_buffer(&data, true)?;
+                let harvesting = if check_harvesting {
+                    Some(
+                        self.0
+                            .get_harvesting()
+                            .map_err(|v| PyException::new_err(v.to_string()))?,
+                    )
+                } else {
+                    None
+                };
+
+                Python::with_gil(|_py| {
+                    let ptr: *mut u8 = data.buf_ptr().cast();
+                    let len = data.len_bytes();
+
+                    let data = unsafe { std::slice::from_raw_parts_mut(ptr, len) };
+                    self.0
+                        .noc_read_checked(
+                            noc_id,
+                            luwen_if::NocCoord::new(x, y),
+                            addr,
+                            data,
+                            harvesting.as_ref(),
+                        )
+                        .map_err(|v| noc_error(v, ""))
+                })
+            }
+
+            /// Same as noc_write but (x, y) is checked against the noc grid before any access is made.
+            /// If check_harvesting is set then harvested tensix cores are also rejected.
+            #[pyo3(signature = (noc_id, x, y, addr, data, check_harvesting = false))]
+            pub fn noc_write_checked(
+                &self,
+                noc_id: u8,
+                x: u8,
+                y: u8,
+                addr: u64,
+                data: pyo3::buffer::PyBuffer<u8>,
+                check_harvesting: bool,
+            ) -> PyResult<()> {
+                check_buffer(&data, false)?;
+                let harvesting = if check_harvesting {
+                    Some(
+                        self.0
+                            .get_harvesting()
+                            .map_err(|v| PyException::new_err(v.to_string()))?,
+                    )
+                } else {
+                    None
+                };
+
+                Python::with_gil(|_py| {
+                    let ptr: *mut u8 = data.buf_ptr().cast();
+                    let len = data.len_bytes();
+
+                    let data = unsafe { std::slice::from_raw_parts(ptr, len) };
+                    self.0
+                        .noc_write_checked(
+                            noc_id,
+                            luwen_if::NocCoord::new(x, y),
+                            addr,
+                            data,
+                            harvesting.as_ref(),
+                        )
+                        .map_err(|v| noc_error(v, ""))
+                })
+            }
+
+            /// Read a list of (noc_id, x, y, addr) locations, releasing the GIL for the whole batch.
+            pub fn noc_read32_batch(
+                &self,
+                py: Python,
+                requests: Vec<(u8, u8, u8, u64)>,
+            ) -> PyResult<Vec<u32>> {
+                py.allow_threads(|| {
+                    let mut output = Vec::with_capacity(requests.len());
+                    for (index, (noc_id, x, y, addr)) in requests.into_iter().enumerate() {
+                        let mut data = [0u8; 4];
+                        self.0.noc_read(noc_id, x, y, addr, &mut data).map_err(|v| {
+                            noc_error(v, &format!("noc_read32_batch failed at index {index}: "))
+                        })?;
+                        output.push(u32::from_le_bytes(data));
+                    }
+
+                    Ok(output)
+                })
+            }
+
+            /// Write a list of (noc_id, x, y, addr, data) locations, releasing the GIL for the whole batch.
+            pub fn noc_write32_batch(
+                &self,
+                py: Python,
+                requests: Vec<(u8, u8, u8, u64, u32)>,
+            ) -> PyResult<()> {
+                py.allow_threads(|| {
+                    for (index, (noc_id, x, y, addr, data)) in requests.into_iter().enumerate() {
+                        self.0
+                            .noc_write(noc_id, x, y, addr, &data.to_le_bytes())
+                            .map_err(|v| {
+                                noc_error(v, &format!("noc_write32_batch failed at index {index}: "))
+                            })?;
+                    }
+
+                    Ok(())
+                })
+            }
+
+            /// Read addr from every tile in the noc grid, tiles that could not be read are returned with None.
+            pub fn noc_sweep32(
+                &self,
+                py: Python,
+                noc_id: u8,
+                addr: u64,
+            ) -> PyResult<Vec<((u8, u8), Option<u32>)>> {
+                let arch = self.0.get_arch();
+                let (grid_size_x, grid_size_y) = arch.grid_size().ok_or_else(|| {
+                    PyException::new_err(format!("Unknown grid size for {arch:?}"))
+                })?;
+
+                py.allow_threads(|| {
+                    let mut output =
+                        Vec::with_capacity(grid_size_x as usize * grid_size_y as usize);
+                    for y in 0..grid_size_y {
+                        for x in 0..grid_size_x {
+                            let mut data = [0u8; 4];
+                            let value = self
+                                .0
+                                .noc_read(noc_id, x, y, addr, &mut data)
+                                .ok()
+                                .map(|_| u32::from_le_bytes(data));
+                            output.push(((x, y), value));
+                        }
+                    }
+
+                    Ok(output)
+                })
+            }
+
+            /// Broadcast data to all tiles on the chip.
+            /// start and end override the corners of the broadcast rectangle and if exclude_harvested
+            /// is set then rows/columns with harvested tensix cores are skipped.
+            #[pyo3(signature = (noc_id, addr, data, exclude_harvested = false, start = None, end = None))]
+            pub fn noc_broadcast(
+                &self,
+                noc_id: u8,
+                addr: u64,
                 data: pyo3::buffer::PyBuffer<u8>,
+                exclude_harvested: bool,
+                start: Option<(u8, u8)>,
+                end: Option<(u8, u8)>,
             ) -> PyResult<()> {
+                check_buffer(&data, false)?;
+                let config = self.broadcast_config(noc_id, exclude_harvested, start, end)?;
                 Python::with_gil(|_py| {
                     let ptr: *mut u8 = data.buf_ptr().cast();
                     let len = data.len_bytes();
 
                     let data = unsafe { std::slice::from_raw_parts(ptr, len) };
                     self.0
-                        .noc_broadcast(noc_id, addr, data)
-                        .map_err(|v| PyException::new_err(v.to_string()))
+                        .noc_broadcast_with_config(noc_id, addr, data, &config)
+                        .map_err(|v| noc_error(v, ""))
                 })
             }
 
-            pub fn noc_broadcast32(&self, noc_id: u8, addr: u64, data: u32) -> PyResult<()> {
+            #[pyo3(signature = (noc_id, addr, data, exclude_harvested = false, start = None, end = None))]
+            pub fn noc_broadcast32(
+                &self,
+                noc_id: u8,
+                addr: u64,
+                data: u32,
+                exclude_harvested: bool,
+                start: Option<(u8, u8)>,
+                end: Option<(u8, u8)>,
+            ) -> PyResult<()> {
+                let config = self.broadcast_config(noc_id, exclude_harvested, start, end)?;
                 self.0
-                    .noc_broadcast(noc_id, addr, &data.to_le_bytes())
-                    .map_err(|v| PyException::new_err(v.to_string()))
+                    .noc_broadcast_with_config(noc_id, addr, &data.to_le_bytes(), &config)
+                    .map_err(|v| noc_error(v, ""))
             }
 
             pub fn axi_translate(&self, addr: &str) -> PyResult<AxiData> {
@@ -408,7 +1454,14 @@ macro_rules! common_chip_comms_impls {
                 }
             }
 
+            pub fn axi_symbols(&self) -> PyResult<Vec<(String, u64, u64)>> {
+                self.0
+                    .axi_symbols()
+                    .map_err(|err| PyException::new_err(err.to_string()))
+            }
+
             pub fn axi_read(&self, addr: u64, data: pyo3::buffer::PyBuffer<u8>) -> PyResult<()> {
+                check_buffer(&data, true)?;
                 Python::with_gil(|_py| {
                     let ptr: *mut u8 = data.buf_ptr().cast();
                     let len = data.len_bytes();
@@ -420,6 +1473,29 @@ macro_rules! common_chip_comms_impls {
                 })
             }
 
+            /// Read a contiguous block of len bytes starting at addr and return it as bytes.
+            /// Both addr and len must be multiples of 4, the whole block is read with a single block read.
+            pub fn axi_read_block(&self, py: Python, addr: u64, len: usize) -> PyResult<Py<PyBytes>> {
+                if addr % 4 != 0 || len % 4 != 0 {
+                    return Err(PyException::new_err(format!(
+                        "axi_read_block needs a 4 byte aligned addr and len, got addr {addr:#x} and len {len}"
+                    )));
+                }
+                if len == 0 || addr.checked_add(len as u64).map_or(true, |end| end > u32::MAX as u64 + 1) {
+                    return Err(PyException::new_err(format!(
+                        "axi_read_block of {len} bytes at {addr:#x} is outside of the axi address space"
+                    )));
+                }
+
+                let bytes = PyBytes::new_with(py, len, |data| {
+                    self.0
+                        .axi_read(addr, data)
+                        .map_err(|v| PyException::new_err(v.to_string()))
+                })?;
+
+                Ok(bytes.into())
+            }
+
             pub fn axi_read32(&self, addr: u64) -> PyResult<u32> {
                 let mut data = [0u8; 4];
                 self.0
@@ -429,12 +1505,41 @@ macro_rules! common_chip_comms_impls {
                 Ok(u32::from_le_bytes(data))
             }
 
+            /// Translate and read each named register.
+            /// Returns the (name, value) pairs that were read and the (name, error) pairs that failed.
+            pub fn axi_read_named(
+                &self,
+                names: Vec<String>,
+            ) -> PyResult<(Vec<(String, u32)>, Vec<(String, String)>)> {
+                let mut values = Vec::with_capacity(names.len());
+                let mut errors = Vec::new();
+                for name in names {
+                    let mut data = [0u8; 4];
+                    let value = match self.0.axi_translate(&name) {
+                        Ok(v) => self
+                            .0
+                            .axi_read(v.addr, &mut data)
+                            .map(|_| u32::from_le_bytes(data))
+                            .map_err(|err| err.to_string()),
+                        Err(err) => Err(err.to_string()),
+                    };
+
+                    match value {
+                        Ok(value) => values.push((name, value)),
+                        Err(err) => errors.push((name, err)),
+                    }
+                }
+
+                Ok((values, errors))
+            }
+
             pub fn axi_write(&self, addr: u64, data: pyo3::buffer::PyBuffer<u8>) -> PyResult<()> {
+                check_buffer(&data, false)?;
                 Python::with_gil(|_py| {
                     let ptr: *mut u8 = data.buf_ptr().cast();
                     let len = data.len_bytes();
 
-                    let data = unsafe { std::slice::from_raw_parts_mut(ptr, len) };
+                    let data = unsafe { std::slice::from_raw_parts(ptr, len) };
                     self.0
                         .axi_write(addr, data)
                         .map_err(|v| PyException::new_err(v.to_string()))
@@ -447,8 +1552,97 @@ macro_rules! common_chip_comms_impls {
                     .map_err(|v| PyException::new_err(v.to_string()))
             }
 
-            #[pyo3(signature = (msg, wait_for_done = true, use_second_mailbox = false, arg0 = 0xffff, arg1 = 0xffff, timeout = 1.0))]
-            pub fn arc_msg(&self, msg: u16, wait_for_done: bool, use_second_mailbox: bool, arg0: u16, arg1: u16, timeout: f64) -> PyResult<Option<(u32, u32)>> {
+            /// Replace the bits of a register selected by mask with those bits of value, returns the value written.
+            /// addr can either be an address or a register name understood by axi_translate.
+            /// The read and the write are done under one access to the device, other users of this handle cannot write in between.
+            pub fn axi_rmw32(&self, addr: AxiAddr, mask: u32, value: u32) -> PyResult<u32> {
+                let addr = match addr {
+                    AxiAddr::Addr(addr) => addr,
+                    AxiAddr::Name(name) => {
+                        self.0
+                            .axi_translate(&name)
+                            .map_err(|v| PyException::new_err(v.to_string()))?
+                            .addr
+                    }
+                };
+
+                self.0
+                    .axi_rmw32(addr, mask, value)
+                    .map_err(|v| PyException::new_err(v.to_string()))
+            }
+
+            /// Replace the bits of a noc register selected by mask with those bits of value, returns the value written.
+            /// The read and the write are done under one access to the device, other users of this handle cannot write in between.
+            pub fn noc_rmw32(
+                &self,
+                noc_id: u8,
+                x: u8,
+                y: u8,
+                addr: u64,
+                mask: u32,
+                value: u32,
+            ) -> PyResult<u32> {
+                self.0
+                    .noc_rmw32(noc_id, x, y, addr, mask, value)
+                    .map_err(|v| noc_error(v, ""))
+            }
+
+            /// Read a noc register until (value & mask) == expected or timeout_secs have passed.
+            /// Returns the last value read and whether it matched.
+            #[allow(clippy::too_many_arguments)]
+            #[pyo3(signature = (noc_id, x, y, addr, mask, expected, timeout_secs = 1.0, poll_interval = 0.001))]
+            pub fn noc_poll32(
+                &self,
+                py: Python,
+                noc_id: u8,
+                x: u8,
+                y: u8,
+                addr: u64,
+                mask: u32,
+                expected: u32,
+                timeout_secs: f64,
+                poll_interval: f64,
+            ) -> PyResult<(u32, bool)> {
+                poll32(py, mask, expected, timeout_secs, poll_interval, || {
+                    self.0
+                        .noc_read32(noc_id, x, y, addr)
+                        .map_err(|v| noc_error(v, ""))
+                })
+            }
+
+            /// Read an axi register until (value & mask) == expected or timeout_secs have passed.
+            /// addr can either be an address or a register name understood by axi_translate.
+            /// Returns the last value read and whether it matched.
+            #[pyo3(signature = (addr, mask, expected, timeout_secs = 1.0, poll_interval = 0.001))]
+            pub fn axi_poll32(
+                &self,
+                py: Python,
+                addr: AxiAddr,
+                mask: u32,
+                expected: u32,
+                timeout_secs: f64,
+                poll_interval: f64,
+            ) -> PyResult<(u32, bool)> {
+                let addr = match addr {
+                    AxiAddr::Addr(addr) => addr,
+                    AxiAddr::Name(name) => {
+                        self.0
+                            .axi_translate(&name)
+                            .map_err(|v| PyException::new_err(v.to_string()))?
+                            .addr
+                    }
+                };
+
+                poll32(py, mask, expected, timeout_secs, poll_interval, || {
+                    self.0
+                        .axi_read32(addr)
+                        .map_err(|v| PyException::new_err(v.to_string()))
+                })
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            #[pyo3(signature = (msg, wait_for_done = true, use_second_mailbox = false, arg0 = 0xffff, arg1 = 0xffff, timeout = 1.0, retries = 0))]
+            pub fn arc_msg(&self, msg: u16, wait_for_done: bool, use_second_mailbox: bool, arg0: u16, arg1: u16, timeout: f64, retries: u32) -> PyResult<Option<(u32, u32)>> {
                 match self.0
                     .arc_msg(ArcMsgOptions {
                         addrs: None,
@@ -456,6 +1650,31 @@ macro_rules! common_chip_comms_impls {
                         wait_for_done,
                         use_second_mailbox,
                         timeout: std::time::Duration::from_secs_f64(timeout),
+                        retries,
+                    }) {
+                        Ok(ArcMsgOk::Ok {rc, arg}) => {
+                            Ok(Some((arg, rc)))
+                        }
+                        Ok(ArcMsgOk::OkNoWait) => {
+                            Ok(None)
+                        }
+                        Err(err) => {
+                            Err(PyException::new_err(err.to_string()))
+                        }
+                    }
+            }
+
+            #[pyo3(signature = (name, args = None, wait_for_done = true, use_second_mailbox = false, timeout = 1.0, retries = 0))]
+            pub fn arc_msg_typed(&self, name: &str, args: Option<Vec<u16>>, wait_for_done: bool, use_second_mailbox: bool, timeout: f64, retries: u32) -> PyResult<Option<(u32, u32)>> {
+                let msg = typed_arc_msg(name, &args.unwrap_or_default())?;
+                match self.0
+                    .arc_msg(ArcMsgOptions {
+                        addrs: None,
+                        msg: ArcMsg::Typed(msg),
+                        wait_for_done,
+                        use_second_mailbox,
+                        timeout: std::time::Duration::from_secs_f64(timeout),
+                        retries,
                     }) {
                         Ok(ArcMsgOk::Ok {rc, arg}) => {
                             Ok(Some((arg, rc)))
@@ -469,10 +1688,120 @@ macro_rules! common_chip_comms_impls {
                     }
             }
 
+            /// Move the arc into one of the A0, A1, A3 or A5 states.
+            pub fn set_arc_state(&self, state: &str) -> PyResult<()> {
+                let state = state.parse::<ArcState>().map_err(PyException::new_err)?;
+                self.0
+                    .arc_msg(ArcMsgOptions {
+                        msg: ArcMsg::Typed(TypedArcMsg::SetArcState { state }),
+                        ..Default::default()
+                    })
+                    .map_err(|v| PyException::new_err(format!("Could not set arc state: {v}")))?;
+
+                Ok(())
+            }
+
+            /// Hault ARC core `core` (0-3), grayskull and wormhole only.
+            /// DANGER: haulting core 0 stops the ARC firmware, arc messages, telemetry and fan/power
+            /// management stop until the core is released. The ARC is moved into A3 first and the
+            /// core is left running if that fails.
+            pub fn arc_core_hault(&self, core: u8) -> PyResult<()> {
+                self.0
+                    .arc_core_hault(core)
+                    .map_err(|v| PyException::new_err(v.to_string()))
+            }
+
+            /// Release an ARC core that was haulted with arc_core_hault.
+            /// Errors if the core is not haulted.
+            pub fn arc_core_release(&self, core: u8) -> PyResult<()> {
+                self.0
+                    .arc_core_release(core)
+                    .map_err(|v| PyException::new_err(v.to_string()))
+            }
+
+            /// Cheap liveness check, returns whether a single read of the arc scratch register succeeded.
+            /// This is the same probe that detect_chips uses to check basic comms.
+            pub fn ping(&self) -> bool {
+                self.0
+                    .axi_sread32(luwen_if::chip::arc_scratch_register(self.0.get_arch()))
+                    .is_ok()
+            }
+
             pub fn get_telemetry(&self) -> PyResult<Telemetry> {
                 self.0.get_telemetry().map(|v| v.into()).map_err(|v| PyException::new_err(v.to_string()))
             }
 
+            /// Read count telemetry snapshots spaced interval_ms apart, the first one is read immediately.
+            /// The GIL is released while reading and waiting, python signals are checked while waiting and between samples.
+            pub fn sample_telemetry(
+                &self,
+                py: Python,
+                count: u32,
+                interval_ms: u32,
+            ) -> PyResult<Vec<Telemetry>> {
+                let interval = std::time::Duration::from_millis(interval_ms as u64);
+                let start = std::time::Instant::now();
+
+                // Sleep in short slices so that a Ctrl-C during a long interval is noticed promptly.
+                const SLEEP_SLICE: std::time::Duration = std::time::Duration::from_millis(50);
+
+                // count comes from the caller, don't let a huge value allocate up front.
+                let mut output = Vec::with_capacity(count.min(1024) as usize);
+                for index in 0..count {
+                    // Samples are scheduled from the start time so that slow reads don't make the rate drift.
+                    let deadline = start + interval * index;
+                    while let Some(wait) = deadline.checked_duration_since(std::time::Instant::now()) {
+                        if wait.is_zero() {
+                            break;
+                        }
+                        py.allow_threads(|| std::thread::sleep(wait.min(SLEEP_SLICE)));
+                        py.check_signals()?;
+                    }
+
+                    let telemetry = py
+                        .allow_threads(|| self.0.get_telemetry().map_err(|v| v.to_string()))
+                        .map_err(|v| {
+                            PyException::new_err(format!("Telemetry sample {index} failed: {v}"))
+                        })?;
+                    output.push(telemetry.into());
+
+                    py.check_signals()?;
+                }
+
+                Ok(output)
+            }
+
+            pub fn get_local_coord(&self) -> PyResult<EthAddr> {
+                self.0
+                    .get_local_chip_coord()
+                    .map(|v| v.into())
+                    .map_err(|v| PyException::new_err(v.to_string()))
+            }
+
+            /// Returns the decoded harvesting mask, all coordinates are noc0 coordinates.
+            pub fn get_harvesting(&self, py: Python) -> PyResult<PyObject> {
+                let harvesting = self
+                    .0
+                    .get_harvesting()
+                    .map_err(|v| PyException::new_err(v.to_string()))?;
+
+                let output = PyDict::new(py);
+                output.set_item("arch", harvesting.arch.to_string())?;
+                output.set_item("harvest_mask", harvesting.mask)?;
+                output.set_item("grid_size", harvesting.grid_size)?;
+                output.set_item("enabled_rows", harvesting.enabled_rows())?;
+                output.set_item("enabled_columns", harvesting.enabled_columns())?;
+                output.set_item("enabled_tensix", harvesting.enabled_tensix())?;
+
+                Ok(output.into())
+            }
+
+            /// Check if the chip is responsive without resetting it.
+            /// On blackhole the reads go through the default noc tlb, which is reprogrammed to point at the arc.
+            pub fn health_check(&self) -> ChipHealth {
+                self.0.health_check().into()
+            }
+
             pub fn get_neighbouring_chips(&self) -> PyResult<Vec<NeighbouringChip>> {
                 self.0
                     .get_neighbouring_chips()
@@ -481,14 +1810,192 @@ macro_rules! common_chip_comms_impls {
             }
 
     }
+
+        impl $name {
+            fn broadcast_config(
+                &self,
+                noc_id: u8,
+                exclude_harvested: bool,
+                start: Option<(u8, u8)>,
+                end: Option<(u8, u8)>,
+            ) -> PyResult<luwen_if::BroadcastConfig> {
+                let mut config = luwen_if::BroadcastConfig {
+                    start,
+                    end,
+                    ..Default::default()
+                };
+
+                // An explicit range that runs into a harvested core can hang the broadcast,
+                // if the harvesting can't be read then only the bounds of the range are checked.
+                let harvesting = if exclude_harvested {
+                    Some(
+                        self.0
+                            .get_harvesting()
+                            .map_err(|v| PyException::new_err(v.to_string()))?,
+                    )
+                } else if start.is_some() || end.is_some() {
+                    self.0.get_harvesting().ok()
+                } else {
+                    None
+                };
+
+                if let (true, Some(harvesting)) = (exclude_harvested, &harvesting) {
+                    config = config.exclude_harvested(noc_id, harvesting);
+                }
+
+                config
+                    .validate(noc_id, self.0.get_arch(), harvesting.as_ref())
+                    .map_err(|v| noc_error(v.into(), "Invalid broadcast range: "))?;
+
+                Ok(config)
+            }
+        }
+}
+}
+
+/// The state passed to a detect callback.
+/// The second field is cleared once the callback returns, after which the chip must not be accessed.
+#[pyclass]
+struct PyChipDetectState(
+    luwen_if::chip::ChipDetectState<'static>,
+    std::sync::Arc<std::sync::atomic::AtomicBool>,
+);
+
+/// Call a python detect callback with `status`.
+fn call_detect_callback(
+    callback: &PyObject,
+    status: luwen_if::chip::ChipDetectState,
+) -> PyResult<()> {
+    // Safety: This is extremly unsafe, the alternative would be to copy the status for
+    // every invocation. The chip handed out by the state is guarded by `valid`.
+    let status = unsafe { std::mem::transmute(status) };
+    let valid = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+
+    // The flag is cleared while the GIL is still held, every DetectingChip method holds the GIL
+    // for its whole call so no access can still be running once detection continues.
+    Python::with_gil(|py| {
+        let result = callback.call1(py, (PyChipDetectState(status, valid.clone()),));
+        valid.store(false, std::sync::atomic::Ordering::Release);
+        result
+    })
+    .map(|_| ())
 }
+
+/// A read only handle to the chip that is currently being detected.
+/// It can only be used from inside of the detect callback that it was taken from,
+/// and only allows reads so that it cannot interfere with the init of the chip.
+#[pyclass]
+pub struct DetectingChip {
+    chip: &'static dyn ChipImpl,
+    valid: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl DetectingChip {
+    fn chip(&self) -> PyResult<&dyn ChipImpl> {
+        if self.valid.load(std::sync::atomic::Ordering::Acquire) {
+            Ok(self.chip)
+        } else {
+            Err(PyException::new_err(
+                "The chip from a detect callback can only be used while the callback is running",
+            ))
+        }
+    }
+}
+
+#[pymethods]
+impl DetectingChip {
+    pub fn arch(&self) -> PyResult<String> {
+        Ok(self.chip()?.get_arch().to_string())
+    }
+
+    pub fn noc_read32(&self, noc_id: u8, x: u8, y: u8, addr: u64) -> PyResult<u32> {
+        let (arc_if, chip_if) = self.chip()?.comms_obj();
+        arc_if
+            .noc_read32(chip_if, noc_id, x, y, addr)
+            .map_err(|v| noc_error(v, ""))
+    }
+
+    pub fn axi_read32(&self, addr: u64) -> PyResult<u32> {
+        let (arc_if, chip_if) = self.chip()?.comms_obj();
+        arc_if
+            .axi_read32(chip_if, addr)
+            .map_err(|v| PyException::new_err(v.to_string()))
+    }
+
+    pub fn axi_sread32(&self, addr: &str) -> PyResult<u32> {
+        let (arc_if, chip_if) = self.chip()?.comms_obj();
+        arc_if
+            .axi_sread32(chip_if, addr)
+            .map_err(|v| PyException::new_err(v.to_string()))
+    }
 }
 
+/// The initialization status of a single chip component (arc, dram, eth or cpu).
 #[pyclass]
-struct PyChipDetectState(luwen_if::chip::ChipDetectState<'static>);
+pub struct ComponentStatus {
+    #[pyo3(get)]
+    name: String,
+    /// The number of sub-components (i.e. dram channels) that are no longer waiting.
+    #[pyo3(get)]
+    done: usize,
+    #[pyo3(get)]
+    total: usize,
+    /// One of "not_present", "waiting", "error" or "done".
+    #[pyo3(get)]
+    state: String,
+    /// The same message that is shown by status_string.
+    #[pyo3(get)]
+    message: String,
+}
+
+impl<P: std::fmt::Display, E: std::fmt::Display> From<&luwen_if::chip::ComponentStatusInfo<P, E>>
+    for ComponentStatus
+{
+    fn from(value: &luwen_if::chip::ComponentStatusInfo<P, E>) -> Self {
+        let state = if !value.is_present() {
+            "not_present"
+        } else if value.is_waiting() {
+            "waiting"
+        } else if value.has_error() {
+            "error"
+        } else {
+            "done"
+        };
+
+        Self {
+            name: value.name.clone(),
+            done: value
+                .wait_status
+                .iter()
+                .filter(|v| !matches!(v, luwen_if::chip::WaitStatus::Waiting(_)))
+                .count(),
+            total: value.wait_status.len(),
+            state: state.to_string(),
+            message: value.to_string(),
+        }
+    }
+}
+
+impl PyChipDetectState {
+    fn init_status(&self) -> Option<&luwen_if::chip::InitStatus> {
+        match self.0.call {
+            luwen_if::chip::CallReason::NewChip | luwen_if::chip::CallReason::NotNew => None,
+            luwen_if::chip::CallReason::ChipInitCompleted(status)
+            | luwen_if::chip::CallReason::InitWait(status) => Some(status),
+        }
+    }
+}
 
 #[pymethods]
 impl PyChipDetectState {
+    /// A read only handle to the chip being detected, it is only valid while the callback is running.
+    pub fn chip(&self) -> DetectingChip {
+        DetectingChip {
+            chip: self.0.chip,
+            valid: self.1.clone(),
+        }
+    }
+
     pub fn new_chip(&self) -> bool {
         matches!(self.0.call, luwen_if::chip::CallReason::NewChip)
     }
@@ -507,6 +2014,22 @@ impl PyChipDetectState {
             )),
         }
     }
+
+    pub fn arc_status(&self) -> Option<ComponentStatus> {
+        self.init_status().map(|v| (&v.arc_status).into())
+    }
+
+    pub fn dram_status(&self) -> Option<ComponentStatus> {
+        self.init_status().map(|v| (&v.dram_status).into())
+    }
+
+    pub fn eth_status(&self) -> Option<ComponentStatus> {
+        self.init_status().map(|v| (&v.eth_status).into())
+    }
+
+    pub fn cpu_status(&self) -> Option<ComponentStatus> {
+        self.init_status().map(|v| (&v.cpu_status).into())
+    }
 }
 
 impl PciChip {
@@ -531,15 +2054,15 @@ impl PciChip {
 #[pymethods]
 impl PciChip {
     pub fn as_wh(&self) -> Option<PciWormhole> {
-        self.0.as_wh().map(|v| PciWormhole(v.clone()))
+        self.0.as_wh().map(|v| PciWormhole::from(v.clone()))
     }
 
     pub fn as_gs(&self) -> Option<PciGrayskull> {
-        self.0.as_gs().map(|v| PciGrayskull(v.clone()))
+        self.0.as_gs().map(|v| PciGrayskull::from(v.clone()))
     }
 
     pub fn as_bh(&self) -> Option<PciBlackhole> {
-        self.0.as_bh().map(|v| PciBlackhole(v.clone()))
+        self.0.as_bh().map(|v| PciBlackhole::from(v.clone()))
     }
 
     pub fn is_remote(&self) -> bool {
@@ -550,15 +2073,33 @@ impl PciChip {
         }
     }
 
+    /// The name of the chip's arch, i.e. "Wormhole".
+    pub fn arch(&self) -> String {
+        self.0.get_arch().to_string()
+    }
+
+    pub fn is_grayskull(&self) -> bool {
+        self.0.get_arch() == Arch::Grayskull
+    }
+
+    pub fn is_wormhole(&self) -> bool {
+        self.0.get_arch() == Arch::Wormhole
+    }
+
+    pub fn is_blackhole(&self) -> bool {
+        self.0.get_arch() == Arch::Blackhole
+    }
+
     #[new]
     pub fn new(pci_interface: Option<usize>) -> PyResult<Self> {
         let pci_interface = pci_interface.unwrap();
 
-        let chip = luwen_ref::ExtendedPciDevice::open(pci_interface).unwrap();
+        let chip = luwen_ref::ExtendedPciDevice::open(pci_interface)
+            .map_err(|v| open_error(v, pci_interface))?;
 
         let arch = chip.borrow().device.arch;
 
-        Ok(PciChip(
+        Ok(PciChip::from(
             luwen_if::chip::Chip::open(
                 arch,
                 luwen_if::CallbackStorage {
@@ -570,29 +2111,85 @@ impl PciChip {
         ))
     }
 
+    /// Open the chip at pci_interface as arch ("grayskull", "wormhole" or "blackhole"),
+    /// ignoring the pci device id that it reports.
+    /// This is meant for bring-up of boards that don't report their final device id,
+    /// forcing the wrong arch will access the chip at the wrong addresses.
+    #[staticmethod]
+    pub fn new_with_arch(py: Python, pci_interface: usize, arch: &str) -> PyResult<Self> {
+        let arch = Arch::from_str(arch)
+            .map_err(|value| PyException::new_err(format!("Could not parse chip arch: {value}")))?;
+        PyErr::warn(
+            py,
+            py.get_type::<pyo3::exceptions::PyUserWarning>(),
+            &format!(
+                "Opening pci device {pci_interface} as {arch} without checking its pci device id"
+            ),
+            1,
+        )?;
+
+        let chip = luwen_ref::ExtendedPciDevice::open_with_arch(pci_interface, arch)
+            .map_err(|v| open_error(v, pci_interface))?;
+
+        Ok(PciChip::from(
+            luwen_if::chip::Chip::open(
+                arch,
+                luwen_if::CallbackStorage {
+                    callback: luwen_ref::comms_callback,
+                    user_data: chip,
+                },
+            )
+            .map_err(|v| PyException::new_err(format!("Could not initialize chip: {v}")))?,
+        ))
+    }
+
+    /// Open the chip at a pci address such as 0000:01:00.0, the domain may be left out.
+    #[staticmethod]
+    pub fn open_by_bdf(bdf: &str) -> PyResult<Self> {
+        let bdf = bdf.trim().to_lowercase();
+        let bdf = if bdf.matches(':').count() == 1 {
+            format!("0000:{bdf}")
+        } else {
+            bdf
+        };
+
+        let mut available = Vec::new();
+        for interface in luwen_ref::PciDevice::scan() {
+            let device_bdf = match luwen_ref::PciDevice::open(interface) {
+                Ok(device) => device.physical.bdf(),
+                Err(_) => continue,
+            };
+
+            if device_bdf == bdf {
+                return Self::new(Some(interface));
+            }
+            available.push(device_bdf);
+        }
+
+        Err(PyException::new_err(format!(
+            "Could not find a device at {bdf}, available devices are [{}]",
+            available.join(", ")
+        )))
+    }
+
     #[pyo3(signature = (callback = None))]
     pub fn init(&mut self, callback: Option<PyObject>) -> PyResult<()> {
         #[allow(clippy::type_complexity)]
         let mut callback: Box<
             dyn FnMut(luwen_if::chip::ChipDetectState) -> Result<(), PyErr>,
         > = if let Some(callback) = callback {
-            Box::new(move |status| {
-                // Safety: This is extremly unsafe, the alternative would be to copy the status for
-                // every invocation.
-                let status = unsafe { std::mem::transmute(status) };
-                if let Err(err) =
-                    Python::with_gil(|py| callback.call1(py, (PyChipDetectState(status),)))
-                {
-                    Err(err)
-                } else {
-                    Ok(())
-                }
-            })
+            Box::new(move |status| call_detect_callback(&callback, status))
         } else {
             Box::new(|_| Python::with_gil(|py| py.check_signals()))
         };
 
-        match wait_for_init(&mut self.0, &mut callback, false, false) {
+        match wait_for_init(
+            &mut self.0,
+            &mut callback,
+            false,
+            false,
+            FailureTolerance::default(),
+        ) {
             Err(InitError::PlatformError(err)) => Err(PyException::new_err(format!(
                 "Could not initialize chip: {err}"
             ))),
@@ -607,6 +2204,44 @@ impl PciChip {
         self.0.inner.get_telemetry().unwrap().board_id
     }
 
+    /// The board serial number reported in telemetry.
+    /// Unlike the pci interface id, the serial number does not change across reboots or resets.
+    pub fn board_serial(&self) -> PyResult<u64> {
+        self.0
+            .get_telemetry()
+            .map(|v| v.board_serial_number())
+            .map_err(|v| PyException::new_err(format!("Could not read board serial: {v}")))
+    }
+
+    /// The init status computed during chip detection, as a dict of component statuses.
+    /// Returns None if the chip was not found through detect_chips.
+    pub fn init_status(&self, py: Python) -> PyResult<PyObject> {
+        match &self.0.init_status {
+            Some(status) => init_status_dict(py, status),
+            None => Ok(py.None()),
+        }
+    }
+
+    /// The size of the noc grid as (x, y) for this chip's arch.
+    pub fn grid_size(&self) -> PyResult<(u8, u8)> {
+        let arch = self.0.get_arch();
+        arch.grid_size()
+            .ok_or_else(|| PyException::new_err(format!("Unknown grid size for {arch}")))
+    }
+
+    /// The number of (columns, rows) of enabled tensix cores after harvesting.
+    pub fn tensix_grid_size(&self) -> PyResult<(usize, usize)> {
+        let harvesting = self
+            .0
+            .get_harvesting()
+            .map_err(|v| PyException::new_err(v.to_string()))?;
+
+        Ok((
+            harvesting.enabled_columns().len(),
+            harvesting.enabled_rows().len(),
+        ))
+    }
+
     pub fn device_id(&self) -> PyResult<u32> {
         let info = self.device_info()?;
         Ok(((info.vendor as u32) << 16) | info.device_id as u32)
@@ -652,14 +2287,88 @@ impl PciGrayskull {
         let value = PciInterface::from_gs(self);
 
         if let Some(value) = value {
-            match ttkmd_if::tlb::Ordering::from(ordering) {
-                ttkmd_if::tlb::Ordering::UNKNOWN(ordering) => Err(PyException::new_err(format!(
-                    "Invalid ordering {ordering}."
-                ))),
-                ordering => Ok(value.setup_tlb(
-                    index, addr, x_start, y_start, x_end, y_end, noc_sel, mcast, ordering, linked,
-                )),
-            }
+            let ordering = parse_ordering(ordering, value.pci_interface.borrow().device.arch)?;
+            value
+                .setup_tlb(
+                    index, addr, x_start, y_start, x_end, y_end, noc_sel, mcast, ordering, linked,
+                )
+                .map_err(PyException::new_err)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Program tlb `index` to point at addr on core (x, y) and return a window that keeps that mapping.
+    #[pyo3(signature = (index, x, y, addr, noc_sel = 0, ordering = 0))]
+    pub fn map_tlb(
+        &self,
+        index: u32,
+        x: u8,
+        y: u8,
+        addr: u64,
+        noc_sel: u8,
+        ordering: u8,
+    ) -> PyResult<TlbWindow> {
+        let value = PciInterface::from_gs(self);
+
+        if let Some(value) = value {
+            let ordering = parse_ordering(ordering, value.pci_interface.borrow().device.arch)?;
+
+            value
+                .map_tlb(
+                    index,
+                    ttkmd_if::Tlb {
+                        local_offset: addr,
+                        x_end: x,
+                        y_end: y,
+                        noc_sel,
+                        ordering,
+                        ..Default::default()
+                    },
+                )
+                .map_err(PyException::new_err)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    pub fn set_default_tlb(&self, index: u32) -> PyResult<()> {
+        let value = PciInterface::from_gs(self);
+
+        if let Some(value) = value {
+            value.pci_interface.borrow_mut().default_tlb = index;
+            Ok(())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Set the ordering used by noc_read/noc_write on this chip (0: relaxed, 1: strict, 2: posted, 3: posted strict, blackhole only).
+    pub fn set_noc_ordering(&self, ordering: u8) -> PyResult<()> {
+        let value = PciInterface::from_gs(self);
+
+        if let Some(value) = value {
+            let arch = value.pci_interface.borrow().device.arch;
+            value.pci_interface.borrow_mut().noc_ordering = parse_ordering(ordering, arch)?;
+            Ok(())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    pub fn get_noc_ordering(&self) -> PyResult<u8> {
+        let value = PciInterface::from_gs(self);
+
+        if let Some(value) = value {
+            Ok(value.pci_interface.borrow().noc_ordering.clone().into())
         } else {
             Err(PyException::new_err(
                 "Could not get PCI interface for this chip.",
@@ -667,12 +2376,16 @@ impl PciGrayskull {
         }
     }
 
-    pub fn set_default_tlb(&self, index: u32) -> PyResult<()> {
+    /// The largest block noc_read/noc_write can move before the default tlb has to be reprogrammed.
+    pub fn max_noc_transfer_size(&self) -> PyResult<u64> {
         let value = PciInterface::from_gs(self);
 
         if let Some(value) = value {
-            value.pci_interface.borrow_mut().default_tlb = index;
-            Ok(())
+            value
+                .pci_interface
+                .borrow()
+                .max_noc_transfer_size()
+                .ok_or_else(|| PyException::new_err("The default tlb is out of range"))
         } else {
             Err(PyException::new_err(
                 "Could not get PCI interface for this chip.",
@@ -717,6 +2430,54 @@ impl PciGrayskull {
         }
     }
 
+    /// Get the (count, size, memory_type) of each TLB window on this device.
+    pub fn get_tlb_info(&self) -> PyResult<Vec<(u64, u64, String)>> {
+        let value = PciInterface::from_gs(self);
+        if let Some(value) = value {
+            Ok(value
+                .tlb_info()
+                .tlb_config
+                .into_iter()
+                .map(|info| (info.count, info.size, memory_type_name(info.memory_type)))
+                .collect())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Get the total number of TLB windows on this device.
+    pub fn get_tlb_total_count(&self) -> PyResult<u32> {
+        let value = PciInterface::from_gs(self);
+        if let Some(value) = value {
+            Ok(value.tlb_info().total_count)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Configure the register that is probed when a read returns 0xffffffff,
+    /// if addr is None the default register for this chip is used.
+    #[pyo3(signature = (enabled, addr = None))]
+    pub fn set_read_checking(&self, enabled: bool, addr: Option<u32>) -> PyResult<()> {
+        let value = PciInterface::from_gs(self);
+        if let Some(value) = value {
+            value
+                .pci_interface
+                .borrow_mut()
+                .device
+                .set_read_checking(enabled, addr);
+            Ok(())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
     pub fn pci_interface_id(&self) -> PyResult<usize> {
         let value = PciInterface::from_gs(self);
         if let Some(value) = value {
@@ -751,10 +2512,68 @@ impl PciGrayskull {
                 .map_err(|v| PyException::new_err(v.to_string()))
         })
     }
+
+    /// Write data to spi and read it back to check that the write landed.
+    pub fn spi_write_verified(&self, addr: u32, data: pyo3::buffer::PyBuffer<u8>) -> PyResult<()> {
+        check_buffer(&data, false)?;
+        Python::with_gil(|_py| {
+            let ptr: *mut u8 = data.buf_ptr().cast();
+            let len = data.len_bytes();
+
+            let data = unsafe { std::slice::from_raw_parts(ptr, len) };
+            spi_write_verified(
+                addr,
+                data,
+                |addr, data| self.0.spi_write(addr, data),
+                |addr, data| self.0.spi_read(addr, data),
+            )
+        })
+    }
+
+    /// Write data to spi in chunks, calling callback(bytes_written) after each chunk.
+    #[pyo3(signature = (addr, data, callback, chunk_size = SPI_CHUNK_SIZE))]
+    pub fn spi_write_progress(
+        &self,
+        py: Python,
+        addr: u32,
+        data: pyo3::buffer::PyBuffer<u8>,
+        callback: PyObject,
+        chunk_size: usize,
+    ) -> PyResult<()> {
+        check_buffer(&data, false)?;
+        let ptr: *mut u8 = data.buf_ptr().cast();
+        let len = data.len_bytes();
+
+        let data = unsafe { std::slice::from_raw_parts(ptr, len) };
+        spi_write_progress(py, addr, data, chunk_size, &callback, |addr, data| {
+            self.0.spi_write(addr, data)
+        })
+    }
+
+    /// Compute the crc32 of a region of spi without copying it into python.
+    pub fn spi_crc32(&self, addr: u32, len: u32) -> PyResult<u32> {
+        spi_crc32(addr, len, |addr, data| self.0.spi_read(addr, data))
+    }
+
+    /// Read a region of spi straight into the file at path, i.e. to back up the rom before reflashing.
+    /// Returns the number of bytes written.
+    pub fn spi_dump_to_file(&self, py: Python, addr: u32, len: u32, path: &str) -> PyResult<u32> {
+        spi_dump_to_file(py, addr, len, path, |addr, data| {
+            self.0.spi_read(addr, data)
+        })
+    }
 }
 
 common_chip_comms_impls!(PciGrayskull);
 
+fn memory_type_name(memory_type: ttkmd_if::tlb::MemoryType) -> String {
+    match memory_type {
+        ttkmd_if::tlb::MemoryType::Uc => "uc",
+        ttkmd_if::tlb::MemoryType::Wc => "wc",
+    }
+    .to_string()
+}
+
 pub struct PciInterface<'a> {
     pub pci_interface: &'a ExtendedPciDeviceWrapper,
 }
@@ -787,6 +2606,25 @@ impl PciInterface<'_> {
             })
     }
 
+    /// The pci device that a local chip is accessed through, None for remote chips
+    /// because they are reached through the device of another chip.
+    pub fn from_chip(chip: &dyn ChipImpl) -> Option<PciInterface<'_>> {
+        if matches!(chip.get_device_info(), Ok(None)) {
+            return None;
+        }
+
+        let (_, chip_if) = chip.comms_obj();
+        let chip_if = match chip_if.as_any().downcast_ref::<NocInterface>() {
+            Some(noc_if) => noc_if.backing.as_any(),
+            None => chip_if.as_any(),
+        };
+        chip_if
+            .downcast_ref::<CallbackStorage<ExtendedPciDeviceWrapper>>()
+            .map(|v| PciInterface {
+                pci_interface: &v.user_data,
+            })
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn setup_tlb(
         &self,
@@ -800,7 +2638,7 @@ impl PciInterface<'_> {
         mcast: bool,
         ordering: ttkmd_if::tlb::Ordering,
         linked: bool,
-    ) -> (u64, u64) {
+    ) -> Result<(u64, u64), String> {
         self.pci_interface
             .borrow_mut()
             .setup_tlb(
@@ -818,7 +2656,36 @@ impl PciInterface<'_> {
                     ..Default::default()
                 },
             )
-            .unwrap()
+            .map_err(|v| v.to_string())
+    }
+
+    pub fn tlb_info(&self) -> ttkmd_if::DeviceTlbInfo {
+        ttkmd_if::tlb::get_tlb_info(&self.pci_interface.borrow().device)
+    }
+
+    pub fn map_tlb(&self, index: u32, tlb: ttkmd_if::Tlb) -> Result<TlbWindow, String> {
+        if index >= self.tlb_info().total_count {
+            return Err(format!("TLB index {index} is out of range"));
+        }
+        if index == self.pci_interface.borrow().default_tlb {
+            return Err(format!(
+                "TLB {index} is the default tlb and is reprogrammed by every chip access"
+            ));
+        }
+
+        let (bar_addr, size) = self
+            .pci_interface
+            .borrow_mut()
+            .setup_tlb(index, tlb.clone())
+            .map_err(|v| v.to_string())?;
+
+        Ok(TlbWindow {
+            device: self.pci_interface.clone(),
+            index,
+            tlb,
+            bar_addr,
+            size,
+        })
     }
 
     pub fn noc_read(&self, tlb_index: u32, addr: u64, data: &mut [u8]) {
@@ -845,6 +2712,78 @@ impl PciInterface<'_> {
         Ok(DmaBuffer(buffer))
     }
 
+    /// Read size bytes from addr on core (x, y) into a newly allocated dma buffer.
+    /// The default tlb is moved across the region and each window is read with a dma transfer.
+    pub fn noc_read_dma(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        size: u32,
+    ) -> Result<DmaBuffer, String> {
+        let borrow: &mut _ = &mut self.pci_interface.borrow_mut();
+        let buffer = borrow
+            .device
+            .allocate_dma_buffer(size)
+            .map_err(|v| v.to_string())?;
+        if buffer.size < size as u64 {
+            return Err(format!(
+                "Allocated dma buffer is {} bytes, but {size} bytes were requested",
+                buffer.size
+            ));
+        }
+
+        let tlb_index = borrow.default_tlb;
+        let mut read = 0;
+        while read < size as u64 {
+            let (bar_addr, slice_len) = borrow
+                .setup_tlb(
+                    tlb_index,
+                    ttkmd_if::Tlb {
+                        local_offset: addr + read,
+                        x_end: x,
+                        y_end: y,
+                        noc_sel: noc_id,
+                        ..Default::default()
+                    },
+                )
+                .map_err(|v| v.to_string())?;
+
+            let to_read = slice_len.min(size as u64 - read);
+            // The dma engine takes a 32 bit chip address, truncating a window in a 64 bit bar
+            // would silently read from whatever is mapped at the low address instead.
+            let chip_addr = ttkmd_if::dma_chip_addr(bar_addr, to_read).ok_or_else(|| {
+                format!(
+                    "TLB {tlb_index} is mapped at bar offset {bar_addr:#x}, which the dma engine can't reach"
+                )
+            })?;
+            borrow
+                .device
+                .pcie_dma_transfer_chunked(
+                    chip_addr,
+                    buffer.physical_address + read,
+                    to_read as u32,
+                    false,
+                )
+                .map_err(|v| v.to_string())?;
+
+            read += to_read;
+        }
+
+        Ok(DmaBuffer(buffer))
+    }
+
+    pub fn dma_capabilities(&self) -> Result<DmaCaps, String> {
+        self.pci_interface
+            .borrow()
+            .device
+            .dma_capabilities()
+            .map(DmaCaps::from)
+            .map_err(|v| v.to_string())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn config_dma(
         &self,
         dma_64_bit_addr: Option<u32>,
@@ -853,6 +2792,7 @@ impl PciInterface<'_> {
         msi: bool,
         read_threshold: u32,
         write_threshold: u32,
+        timeout: std::time::Duration,
     ) -> Result<(), String> {
         let borrow: &mut _ = &mut self.pci_interface.borrow_mut();
         borrow.device.dma_config = Some(DmaConfig {
@@ -863,6 +2803,7 @@ impl PciInterface<'_> {
             use_msi_for_dma: msi,
             read_threshold,
             write_threshold,
+            completion_timeout: timeout,
         });
 
         Ok(())
@@ -882,6 +2823,20 @@ impl PciInterface<'_> {
             .map_err(|v| v.to_string())
     }
 
+    pub fn dma_transfer_chunked(
+        &self,
+        addr: u32,
+        physical_address: u64,
+        size: u32,
+        write: bool,
+    ) -> Result<(), String> {
+        let borrow: &mut _ = &mut self.pci_interface.borrow_mut();
+        borrow
+            .device
+            .pcie_dma_transfer_chunked(addr, physical_address, size, write)
+            .map_err(|v| v.to_string())
+    }
+
     pub fn axi_write32(&self, addr: u32, value: u32) -> Result<(), String> {
         let borrow: &mut _ = &mut self.pci_interface.borrow_mut();
         borrow
@@ -920,47 +2875,248 @@ impl From<luwen_if::EthAddr> for EthAddr {
     }
 }
 
+impl From<&EthAddr> for luwen_if::EthAddr {
+    fn from(value: &EthAddr) -> Self {
+        Self {
+            shelf_x: value.shelf_x,
+            shelf_y: value.shelf_y,
+            rack_x: value.rack_x,
+            rack_y: value.rack_y,
+        }
+    }
+}
+
+#[pymethods]
+impl EthAddr {
+    #[new]
+    pub fn new(shelf_x: u8, shelf_y: u8, rack_x: u8, rack_y: u8) -> Self {
+        Self {
+            shelf_x,
+            shelf_y,
+            rack_x,
+            rack_y,
+        }
+    }
+
+    /// Parse an address in the format returned by str(), "[rack_x, rack_y, shelf_x, shelf_y]".
+    #[classmethod]
+    pub fn from_str(_cls: &PyType, value: &str) -> PyResult<Self> {
+        value
+            .parse::<luwen_if::EthAddr>()
+            .map(EthAddr::from)
+            .map_err(PyException::new_err)
+    }
+
+    pub fn __str__(&self) -> String {
+        luwen_if::EthAddr::from(self).to_string()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "EthAddr(shelf_x={}, shelf_y={}, rack_x={}, rack_y={})",
+            self.shelf_x, self.shelf_y, self.rack_x, self.rack_y
+        )
+    }
+}
+
 #[pymethods]
 impl PciWormhole {
+    /// Open the remote chip at the given ethernet address, unset parts of the address are taken from this chip.
+    /// The remote is probed with a single ethernet read before it is opened so that an unreachable chip
+    /// is reported promptly. `timeout_secs` bounds that probe, once it answers the chip is opened with the usual timeout.
+    #[pyo3(signature = (rack_x = None, rack_y = None, shelf_x = None, shelf_y = None, timeout_secs = None))]
     pub fn open_remote(
         &self,
         rack_x: Option<u8>,
         rack_y: Option<u8>,
         shelf_x: Option<u8>,
         shelf_y: Option<u8>,
+        timeout_secs: Option<f64>,
     ) -> PyResult<RemoteWormhole> {
-        Ok(RemoteWormhole(
+        let addr = (rack_x, rack_y, shelf_x, shelf_y);
+        let timeout = timeout_secs
+            .map(std::time::Duration::try_from_secs_f64)
+            .transpose()
+            .map_err(|v| PyException::new_err(format!("Invalid timeout: {v}")))?;
+        self.0
+            .probe_remote(addr, timeout)
+            .and_then(|_| self.0.open_remote(addr))
+            .map(RemoteWormhole::from)
+            .map_err(|v| PyException::new_err(format!("Could not open remote: {}", v)))
+    }
+
+    /// Open the remote chip at a fully specified ethernet address,
+    /// raises an exception if that chip can't be reached from this chip.
+    pub fn open_remote_abs(&self, eth_addr: EthAddr) -> PyResult<RemoteWormhole> {
+        Ok(RemoteWormhole::from(
             self.0
-                .open_remote((rack_x, rack_y, shelf_x, shelf_y))
+                .open_remote_abs(luwen_if::EthAddr::from(&eth_addr))
                 .map_err(|v| PyException::new_err(format!("Could not open remote: {}", v)))?,
         ))
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn setup_tlb(
-        &mut self,
-        index: u32,
-        addr: u64,
-        x_start: u8,
-        y_start: u8,
-        x_end: u8,
-        y_end: u8,
-        noc_sel: u8,
-        mcast: bool,
-        ordering: u8,
-        linked: bool,
-    ) -> PyResult<(u64, u64)> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn setup_tlb(
+        &mut self,
+        index: u32,
+        addr: u64,
+        x_start: u8,
+        y_start: u8,
+        x_end: u8,
+        y_end: u8,
+        noc_sel: u8,
+        mcast: bool,
+        ordering: u8,
+        linked: bool,
+    ) -> PyResult<(u64, u64)> {
+        let value = PciInterface::from_wh(self);
+
+        if let Some(value) = value {
+            let ordering = parse_ordering(ordering, value.pci_interface.borrow().device.arch)?;
+            value
+                .setup_tlb(
+                    index, addr, x_start, y_start, x_end, y_end, noc_sel, mcast, ordering, linked,
+                )
+                .map_err(PyException::new_err)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Program tlb `index` to point at addr on core (x, y) and return a window that keeps that mapping.
+    #[pyo3(signature = (index, x, y, addr, noc_sel = 0, ordering = 0))]
+    pub fn map_tlb(
+        &self,
+        index: u32,
+        x: u8,
+        y: u8,
+        addr: u64,
+        noc_sel: u8,
+        ordering: u8,
+    ) -> PyResult<TlbWindow> {
+        let value = PciInterface::from_wh(self);
+
+        if let Some(value) = value {
+            let ordering = parse_ordering(ordering, value.pci_interface.borrow().device.arch)?;
+
+            value
+                .map_tlb(
+                    index,
+                    ttkmd_if::Tlb {
+                        local_offset: addr,
+                        x_end: x,
+                        y_end: y,
+                        noc_sel,
+                        ordering,
+                        ..Default::default()
+                    },
+                )
+                .map_err(PyException::new_err)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    pub fn set_default_tlb(&self, index: u32) -> PyResult<()> {
+        let value = PciInterface::from_wh(self);
+
+        if let Some(value) = value {
+            value.pci_interface.borrow_mut().default_tlb = index;
+            Ok(())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Set the ordering used by noc_read/noc_write on this chip (0: relaxed, 1: strict, 2: posted, 3: posted strict, blackhole only).
+    pub fn set_noc_ordering(&self, ordering: u8) -> PyResult<()> {
+        let value = PciInterface::from_wh(self);
+
+        if let Some(value) = value {
+            let arch = value.pci_interface.borrow().device.arch;
+            value.pci_interface.borrow_mut().noc_ordering = parse_ordering(ordering, arch)?;
+            Ok(())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    pub fn get_noc_ordering(&self) -> PyResult<u8> {
+        let value = PciInterface::from_wh(self);
+
+        if let Some(value) = value {
+            Ok(value.pci_interface.borrow().noc_ordering.clone().into())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// The largest block noc_read/noc_write can move before the default tlb has to be reprogrammed.
+    pub fn max_noc_transfer_size(&self) -> PyResult<u64> {
+        let value = PciInterface::from_wh(self);
+
+        if let Some(value) = value {
+            value
+                .pci_interface
+                .borrow()
+                .max_noc_transfer_size()
+                .ok_or_else(|| PyException::new_err("The default tlb is out of range"))
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Read a register directly through the pci bar, this bypasses the noc and arc
+    /// so it can be used to recover a chip whose noc is hung.
+    pub fn pci_axi_read32(&self, addr: u32) -> PyResult<u32> {
+        let value = PciInterface::from_wh(self);
+        if let Some(value) = value {
+            value
+                .axi_read32(addr)
+                .map_err(|v| PyException::new_err(v.to_string()))
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Write a register directly through the pci bar, this bypasses the noc and arc
+    /// so it can be used to recover a chip whose noc is hung.
+    pub fn pci_axi_write32(&self, addr: u32, data: u32) -> PyResult<()> {
+        let value = PciInterface::from_wh(self);
+        if let Some(value) = value {
+            value
+                .axi_write32(addr, data)
+                .map_err(|v| PyException::new_err(v.to_string()))
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Set how long (in seconds) to wait for reads and writes to remote chips over ethernet.
+    pub fn set_eth_timeout(&self, timeout: f64) -> PyResult<()> {
         let value = PciInterface::from_wh(self);
 
         if let Some(value) = value {
-            match ttkmd_if::tlb::Ordering::from(ordering) {
-                ttkmd_if::tlb::Ordering::UNKNOWN(ordering) => Err(PyException::new_err(format!(
-                    "Invalid ordering {ordering}."
-                ))),
-                ordering => Ok(value.setup_tlb(
-                    index, addr, x_start, y_start, x_end, y_end, noc_sel, mcast, ordering, linked,
-                )),
-            }
+            value.pci_interface.borrow_mut().eth_timeout =
+                std::time::Duration::try_from_secs_f64(timeout)
+                    .map_err(|v| PyException::new_err(format!("Invalid timeout: {v}")))?;
+            Ok(())
         } else {
             Err(PyException::new_err(
                 "Could not get PCI interface for this chip.",
@@ -968,11 +3124,17 @@ impl PciWormhole {
         }
     }
 
-    pub fn set_default_tlb(&self, index: u32) -> PyResult<()> {
+    /// Set how many ethernet cores keep their own 1 MB dma buffer, when another core needs one the least
+    /// recently used buffer is reused. Buffers are only freed when the device is closed. At least one buffer is always kept.
+    pub fn set_eth_dma_buffer_limit(&self, count: usize) -> PyResult<()> {
         let value = PciInterface::from_wh(self);
 
         if let Some(value) = value {
-            value.pci_interface.borrow_mut().default_tlb = index;
+            value
+                .pci_interface
+                .borrow_mut()
+                .ethernet_dma_buffer
+                .set_capacity(count);
             Ok(())
         } else {
             Err(PyException::new_err(
@@ -995,7 +3157,47 @@ impl PciWormhole {
         }
     }
 
-    #[pyo3(signature = (dma_64_bit_addr, csm_pcie_ctrl_dma_request_offset, arc_misc_cntl_addr, msi, read_threshold, write_threshold))]
+    /// Read a noc region directly into a new dma buffer, dma must already be configured with config_dma.
+    pub fn noc_read_dma(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        size: u32,
+    ) -> PyResult<DmaBuffer> {
+        let value = PciInterface::from_wh(self);
+
+        if let Some(value) = value {
+            Ok(value
+                .noc_read_dma(noc_id, x, y, addr, size)
+                .map_err(|v| PyException::new_err(format!("Could not perform dma read: {}", v)))?)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Report the max dma address width, buffer size and msi support of this chip,
+    /// use this to pick the arguments for config_dma.
+    pub fn dma_capabilities(&self) -> PyResult<DmaCaps> {
+        let value = PciInterface::from_wh(self);
+
+        if let Some(value) = value {
+            value.dma_capabilities().map_err(|v| {
+                PyException::new_err(format!("Could not read dma capabilities: {}", v))
+            })
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// `timeout` is the number of seconds to wait for each dma transfer to complete.
+    #[pyo3(signature = (dma_64_bit_addr, csm_pcie_ctrl_dma_request_offset, arc_misc_cntl_addr, msi, read_threshold, write_threshold, timeout = 1.0))]
+    #[allow(clippy::too_many_arguments)]
     pub fn config_dma(
         &self,
         dma_64_bit_addr: Option<u32>,
@@ -1004,7 +3206,10 @@ impl PciWormhole {
         msi: bool,
         read_threshold: u32,
         write_threshold: u32,
+        timeout: f64,
     ) -> PyResult<()> {
+        let timeout = std::time::Duration::try_from_secs_f64(timeout)
+            .map_err(|v| PyException::new_err(format!("Invalid timeout: {v}")))?;
         let value = PciInterface::from_wh(self);
 
         if let Some(value) = value {
@@ -1016,6 +3221,7 @@ impl PciWormhole {
                     msi,
                     read_threshold,
                     write_threshold,
+                    timeout,
                 )
                 .map_err(|v| PyException::new_err(format!("Could perform dma config: {}", v)))?)
         } else {
@@ -1045,6 +3251,27 @@ impl PciWormhole {
         }
     }
 
+    /// Like dma_transfer_turbo, but splits transfers larger than the maximum dma size into multiple transfers.
+    pub fn dma_transfer_chunked(
+        &self,
+        addr: u32,
+        physical_dma_buffer: u64,
+        size: u32,
+        write: bool,
+    ) -> PyResult<()> {
+        let value = PciInterface::from_wh(self);
+
+        if let Some(value) = value {
+            Ok(value
+                .dma_transfer_chunked(addr, physical_dma_buffer, size, write)
+                .map_err(|v| PyException::new_err(format!("Could perform dma transfer: {}", v)))?)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
     pub fn pci_board_type(&self) -> PyResult<u16> {
         let value = PciInterface::from_wh(self);
         if let Some(value) = value {
@@ -1056,6 +3283,54 @@ impl PciWormhole {
         }
     }
 
+    /// Get the (count, size, memory_type) of each TLB window on this device.
+    pub fn get_tlb_info(&self) -> PyResult<Vec<(u64, u64, String)>> {
+        let value = PciInterface::from_wh(self);
+        if let Some(value) = value {
+            Ok(value
+                .tlb_info()
+                .tlb_config
+                .into_iter()
+                .map(|info| (info.count, info.size, memory_type_name(info.memory_type)))
+                .collect())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Get the total number of TLB windows on this device.
+    pub fn get_tlb_total_count(&self) -> PyResult<u32> {
+        let value = PciInterface::from_wh(self);
+        if let Some(value) = value {
+            Ok(value.tlb_info().total_count)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Configure the register that is probed when a read returns 0xffffffff,
+    /// if addr is None the default register for this chip is used.
+    #[pyo3(signature = (enabled, addr = None))]
+    pub fn set_read_checking(&self, enabled: bool, addr: Option<u32>) -> PyResult<()> {
+        let value = PciInterface::from_wh(self);
+        if let Some(value) = value {
+            value
+                .pci_interface
+                .borrow_mut()
+                .device
+                .set_read_checking(enabled, addr);
+            Ok(())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
     pub fn pci_interface_id(&self) -> PyResult<usize> {
         let value = PciInterface::from_wh(self);
         if let Some(value) = value {
@@ -1091,10 +3366,60 @@ impl PciWormhole {
         })
     }
 
-    pub fn get_local_coord(&self) -> PyResult<EthAddr> {
+    /// Write data to spi and read it back to check that the write landed.
+    pub fn spi_write_verified(&self, addr: u32, data: pyo3::buffer::PyBuffer<u8>) -> PyResult<()> {
+        check_buffer(&data, false)?;
+        Python::with_gil(|_py| {
+            let ptr: *mut u8 = data.buf_ptr().cast();
+            let len = data.len_bytes();
+
+            let data = unsafe { std::slice::from_raw_parts(ptr, len) };
+            spi_write_verified(
+                addr,
+                data,
+                |addr, data| self.0.spi_write(addr, data),
+                |addr, data| self.0.spi_read(addr, data),
+            )
+        })
+    }
+
+    /// Write data to spi in chunks, calling callback(bytes_written) after each chunk.
+    #[pyo3(signature = (addr, data, callback, chunk_size = SPI_CHUNK_SIZE))]
+    pub fn spi_write_progress(
+        &self,
+        py: Python,
+        addr: u32,
+        data: pyo3::buffer::PyBuffer<u8>,
+        callback: PyObject,
+        chunk_size: usize,
+    ) -> PyResult<()> {
+        check_buffer(&data, false)?;
+        let ptr: *mut u8 = data.buf_ptr().cast();
+        let len = data.len_bytes();
+
+        let data = unsafe { std::slice::from_raw_parts(ptr, len) };
+        spi_write_progress(py, addr, data, chunk_size, &callback, |addr, data| {
+            self.0.spi_write(addr, data)
+        })
+    }
+
+    /// Compute the crc32 of a region of spi without copying it into python.
+    pub fn spi_crc32(&self, addr: u32, len: u32) -> PyResult<u32> {
+        spi_crc32(addr, len, |addr, data| self.0.spi_read(addr, data))
+    }
+
+    /// Read a region of spi straight into the file at path, i.e. to back up the rom before reflashing.
+    /// Returns the number of bytes written.
+    pub fn spi_dump_to_file(&self, py: Python, addr: u32, len: u32, path: &str) -> PyResult<u32> {
+        spi_dump_to_file(py, addr, len, path, |addr, data| {
+            self.0.spi_read(addr, data)
+        })
+    }
+
+    /// Check whether noc coordinate translation is enabled without reading the full telemetry.
+    pub fn noc_translation_enabled(&self) -> PyResult<bool> {
         self.0
-            .get_local_chip_coord()
-            .map(|v| v.into())
+            .noc_translation_enabled()
             .map_err(|v| PyException::new_err(v.to_string()))
     }
 }
@@ -1102,10 +3427,11 @@ impl PciWormhole {
 common_chip_comms_impls!(PciWormhole);
 
 #[pyclass]
-pub struct RemoteWormhole(luwen_if::chip::Wormhole);
+pub struct RemoteWormhole(luwen_if::chip::Wormhole, LazyChipIdentity);
 
 common_chip_comms_impls!(RemoteWormhole);
 
+#[pymethods]
 impl RemoteWormhole {
     pub fn spi_read(&self, addr: u32, data: pyo3::buffer::PyBuffer<u8>) -> PyResult<()> {
         Python::with_gil(|_py| {
@@ -1131,16 +3457,71 @@ impl RemoteWormhole {
         })
     }
 
-    pub fn get_local_coord(&self) -> PyResult<EthAddr> {
+    /// Write data to spi and read it back to check that the write landed.
+    pub fn spi_write_verified(&self, addr: u32, data: pyo3::buffer::PyBuffer<u8>) -> PyResult<()> {
+        check_buffer(&data, false)?;
+        Python::with_gil(|_py| {
+            let ptr: *mut u8 = data.buf_ptr().cast();
+            let len = data.len_bytes();
+
+            let data = unsafe { std::slice::from_raw_parts(ptr, len) };
+            spi_write_verified(
+                addr,
+                data,
+                |addr, data| self.0.spi_write(addr, data),
+                |addr, data| self.0.spi_read(addr, data),
+            )
+        })
+    }
+
+    /// Write data to spi in chunks, calling callback(bytes_written) after each chunk.
+    #[pyo3(signature = (addr, data, callback, chunk_size = SPI_CHUNK_SIZE))]
+    pub fn spi_write_progress(
+        &self,
+        py: Python,
+        addr: u32,
+        data: pyo3::buffer::PyBuffer<u8>,
+        callback: PyObject,
+        chunk_size: usize,
+    ) -> PyResult<()> {
+        check_buffer(&data, false)?;
+        let ptr: *mut u8 = data.buf_ptr().cast();
+        let len = data.len_bytes();
+
+        let data = unsafe { std::slice::from_raw_parts(ptr, len) };
+        spi_write_progress(py, addr, data, chunk_size, &callback, |addr, data| {
+            self.0.spi_write(addr, data)
+        })
+    }
+
+    /// Compute the crc32 of a region of spi without copying it into python.
+    pub fn spi_crc32(&self, addr: u32, len: u32) -> PyResult<u32> {
+        spi_crc32(addr, len, |addr, data| self.0.spi_read(addr, data))
+    }
+
+    /// Read a region of spi straight into the file at path, i.e. to back up the rom before reflashing.
+    /// Returns the number of bytes written.
+    pub fn spi_dump_to_file(&self, py: Python, addr: u32, len: u32, path: &str) -> PyResult<u32> {
+        spi_dump_to_file(py, addr, len, path, |addr, data| {
+            self.0.spi_read(addr, data)
+        })
+    }
+
+    /// Check whether noc coordinate translation is enabled without reading the full telemetry.
+    pub fn noc_translation_enabled(&self) -> PyResult<bool> {
         self.0
-            .get_local_chip_coord()
-            .map(|v| v.into())
+            .noc_translation_enabled()
             .map_err(|v| PyException::new_err(v.to_string()))
     }
 }
 
 #[pymethods]
 impl PciBlackhole {
+    /// Return the number of enabled tensix columns, ethernet ports, gddr channels, l2cpus and pcie instances.
+    pub fn resource_layout(&self, py: Python) -> PyResult<PyObject> {
+        resource_layout_dict(py, &self.0)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn setup_tlb(
         &mut self,
@@ -1158,14 +3539,149 @@ impl PciBlackhole {
         let value = PciInterface::from_bh(self);
 
         if let Some(value) = value {
-            match ttkmd_if::tlb::Ordering::from(ordering) {
-                ttkmd_if::tlb::Ordering::UNKNOWN(ordering) => Err(PyException::new_err(format!(
-                    "Invalid ordering {ordering}."
-                ))),
-                ordering => Ok(value.setup_tlb(
+            let ordering = parse_ordering(ordering, value.pci_interface.borrow().device.arch)?;
+            value
+                .setup_tlb(
                     index, addr, x_start, y_start, x_end, y_end, noc_sel, mcast, ordering, linked,
-                )),
-            }
+                )
+                .map_err(PyException::new_err)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Program tlb `index` to point at addr on core (x, y) and return a window that keeps that mapping.
+    #[pyo3(signature = (index, x, y, addr, noc_sel = 0, ordering = 0))]
+    pub fn map_tlb(
+        &self,
+        index: u32,
+        x: u8,
+        y: u8,
+        addr: u64,
+        noc_sel: u8,
+        ordering: u8,
+    ) -> PyResult<TlbWindow> {
+        let value = PciInterface::from_bh(self);
+
+        if let Some(value) = value {
+            let ordering = parse_ordering(ordering, value.pci_interface.borrow().device.arch)?;
+
+            value
+                .map_tlb(
+                    index,
+                    ttkmd_if::Tlb {
+                        local_offset: addr,
+                        x_end: x,
+                        y_end: y,
+                        noc_sel,
+                        ordering,
+                        ..Default::default()
+                    },
+                )
+                .map_err(PyException::new_err)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    pub fn set_default_tlb(&self, index: u32) -> PyResult<()> {
+        let value = PciInterface::from_bh(self);
+
+        if let Some(value) = value {
+            value.pci_interface.borrow_mut().default_tlb = index;
+            Ok(())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Set the ordering used by noc_read/noc_write on this chip (0: relaxed, 1: strict, 2: posted, 3: posted strict).
+    pub fn set_noc_ordering(&self, ordering: u8) -> PyResult<()> {
+        let value = PciInterface::from_bh(self);
+
+        if let Some(value) = value {
+            let arch = value.pci_interface.borrow().device.arch;
+            value.pci_interface.borrow_mut().noc_ordering = parse_ordering(ordering, arch)?;
+            Ok(())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    pub fn get_noc_ordering(&self) -> PyResult<u8> {
+        let value = PciInterface::from_bh(self);
+
+        if let Some(value) = value {
+            Ok(value.pci_interface.borrow().noc_ordering.clone().into())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// The largest block noc_read/noc_write can move before the default tlb has to be reprogrammed.
+    pub fn max_noc_transfer_size(&self) -> PyResult<u64> {
+        let value = PciInterface::from_bh(self);
+
+        if let Some(value) = value {
+            value
+                .pci_interface
+                .borrow()
+                .max_noc_transfer_size()
+                .ok_or_else(|| PyException::new_err("The default tlb is out of range"))
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Read a register directly through the pci bar, this bypasses the noc and arc
+    /// so it can be used to recover a chip whose noc is hung.
+    pub fn pci_axi_read32(&self, addr: u32) -> PyResult<u32> {
+        let value = PciInterface::from_bh(self);
+        if let Some(value) = value {
+            value
+                .axi_read32(addr)
+                .map_err(|v| PyException::new_err(v.to_string()))
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Write a register directly through the pci bar, this bypasses the noc and arc
+    /// so it can be used to recover a chip whose noc is hung.
+    pub fn pci_axi_write32(&self, addr: u32, data: u32) -> PyResult<()> {
+        let value = PciInterface::from_bh(self);
+        if let Some(value) = value {
+            value
+                .axi_write32(addr, data)
+                .map_err(|v| PyException::new_err(v.to_string()))
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    pub fn allocate_dma_buffer(&self, size: u32) -> PyResult<DmaBuffer> {
+        let value = PciInterface::from_bh(self);
+
+        if let Some(value) = value {
+            Ok(value.allocate_dma_buffer(size).map_err(|v| {
+                PyException::new_err(format!("Could not allocate DMA buffer: {}", v))
+            })?)
         } else {
             Err(PyException::new_err(
                 "Could not get PCI interface for this chip.",
@@ -1173,12 +3689,21 @@ impl PciBlackhole {
         }
     }
 
-    pub fn set_default_tlb(&self, index: u32) -> PyResult<()> {
+    /// Read a noc region directly into a new dma buffer, dma must already be configured with config_dma.
+    pub fn noc_read_dma(
+        &self,
+        noc_id: u8,
+        x: u8,
+        y: u8,
+        addr: u64,
+        size: u32,
+    ) -> PyResult<DmaBuffer> {
         let value = PciInterface::from_bh(self);
 
         if let Some(value) = value {
-            value.pci_interface.borrow_mut().default_tlb = index;
-            Ok(())
+            Ok(value
+                .noc_read_dma(noc_id, x, y, addr, size)
+                .map_err(|v| PyException::new_err(format!("Could not perform dma read: {}", v)))?)
         } else {
             Err(PyException::new_err(
                 "Could not get PCI interface for this chip.",
@@ -1186,13 +3711,15 @@ impl PciBlackhole {
         }
     }
 
-    pub fn allocate_dma_buffer(&self, size: u32) -> PyResult<DmaBuffer> {
+    /// Report the max dma address width, buffer size and msi support of this chip,
+    /// use this to pick the arguments for config_dma.
+    pub fn dma_capabilities(&self) -> PyResult<DmaCaps> {
         let value = PciInterface::from_bh(self);
 
         if let Some(value) = value {
-            Ok(value.allocate_dma_buffer(size).map_err(|v| {
-                PyException::new_err(format!("Could not allocate DMA buffer: {}", v))
-            })?)
+            value.dma_capabilities().map_err(|v| {
+                PyException::new_err(format!("Could not read dma capabilities: {}", v))
+            })
         } else {
             Err(PyException::new_err(
                 "Could not get PCI interface for this chip.",
@@ -1200,7 +3727,9 @@ impl PciBlackhole {
         }
     }
 
-    #[pyo3(signature = (dma_64_bit_addr, csm_pcie_ctrl_dma_request_offset, arc_misc_cntl_addr, msi, read_threshold, write_threshold))]
+    /// `timeout` is the number of seconds to wait for each dma transfer to complete.
+    #[pyo3(signature = (dma_64_bit_addr, csm_pcie_ctrl_dma_request_offset, arc_misc_cntl_addr, msi, read_threshold, write_threshold, timeout = 1.0))]
+    #[allow(clippy::too_many_arguments)]
     pub fn config_dma(
         &self,
         dma_64_bit_addr: Option<u32>,
@@ -1209,7 +3738,10 @@ impl PciBlackhole {
         msi: bool,
         read_threshold: u32,
         write_threshold: u32,
+        timeout: f64,
     ) -> PyResult<()> {
+        let timeout = std::time::Duration::try_from_secs_f64(timeout)
+            .map_err(|v| PyException::new_err(format!("Invalid timeout: {v}")))?;
         let value = PciInterface::from_bh(self);
 
         if let Some(value) = value {
@@ -1221,6 +3753,7 @@ impl PciBlackhole {
                     msi,
                     read_threshold,
                     write_threshold,
+                    timeout,
                 )
                 .map_err(|v| PyException::new_err(format!("Could perform dma config: {}", v)))?)
         } else {
@@ -1250,6 +3783,27 @@ impl PciBlackhole {
         }
     }
 
+    /// Like dma_transfer_turbo, but splits transfers larger than the maximum dma size into multiple transfers.
+    pub fn dma_transfer_chunked(
+        &self,
+        addr: u32,
+        physical_dma_buffer: u64,
+        size: u32,
+        write: bool,
+    ) -> PyResult<()> {
+        let value = PciInterface::from_bh(self);
+
+        if let Some(value) = value {
+            Ok(value
+                .dma_transfer_chunked(addr, physical_dma_buffer, size, write)
+                .map_err(|v| PyException::new_err(format!("Could perform dma transfer: {}", v)))?)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
     pub fn pci_board_type(&self) -> PyResult<u16> {
         let value = PciInterface::from_bh(self);
         if let Some(value) = value {
@@ -1261,6 +3815,54 @@ impl PciBlackhole {
         }
     }
 
+    /// Get the (count, size, memory_type) of each TLB window on this device.
+    pub fn get_tlb_info(&self) -> PyResult<Vec<(u64, u64, String)>> {
+        let value = PciInterface::from_bh(self);
+        if let Some(value) = value {
+            Ok(value
+                .tlb_info()
+                .tlb_config
+                .into_iter()
+                .map(|info| (info.count, info.size, memory_type_name(info.memory_type)))
+                .collect())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Get the total number of TLB windows on this device.
+    pub fn get_tlb_total_count(&self) -> PyResult<u32> {
+        let value = PciInterface::from_bh(self);
+        if let Some(value) = value {
+            Ok(value.tlb_info().total_count)
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
+    /// Configure the register that is probed when a read returns 0xffffffff,
+    /// if addr is None the default register for this chip is used.
+    #[pyo3(signature = (enabled, addr = None))]
+    pub fn set_read_checking(&self, enabled: bool, addr: Option<u32>) -> PyResult<()> {
+        let value = PciInterface::from_bh(self);
+        if let Some(value) = value {
+            value
+                .pci_interface
+                .borrow_mut()
+                .device
+                .set_read_checking(enabled, addr);
+            Ok(())
+        } else {
+            Err(PyException::new_err(
+                "Could not get PCI interface for this chip.",
+            ))
+        }
+    }
+
     pub fn pci_interface_id(&self) -> PyResult<usize> {
         let value = PciInterface::from_bh(self);
         if let Some(value) = value {
@@ -1296,11 +3898,54 @@ impl PciBlackhole {
         })
     }
 
-    pub fn get_local_coord(&self) -> PyResult<EthAddr> {
-        self.0
-            .get_local_chip_coord()
-            .map(|v| v.into())
-            .map_err(|v| PyException::new_err(v.to_string()))
+    /// Write data to spi and read it back to check that the write landed.
+    pub fn spi_write_verified(&self, addr: u32, data: pyo3::buffer::PyBuffer<u8>) -> PyResult<()> {
+        check_buffer(&data, false)?;
+        Python::with_gil(|_py| {
+            let ptr: *mut u8 = data.buf_ptr().cast();
+            let len = data.len_bytes();
+
+            let data = unsafe { std::slice::from_raw_parts(ptr, len) };
+            spi_write_verified(
+                addr,
+                data,
+                |addr, data| self.0.spi_write(addr, data),
+                |addr, data| self.0.spi_read(addr, data),
+            )
+        })
+    }
+
+    /// Write data to spi in chunks, calling callback(bytes_written) after each chunk.
+    #[pyo3(signature = (addr, data, callback, chunk_size = SPI_CHUNK_SIZE))]
+    pub fn spi_write_progress(
+        &self,
+        py: Python,
+        addr: u32,
+        data: pyo3::buffer::PyBuffer<u8>,
+        callback: PyObject,
+        chunk_size: usize,
+    ) -> PyResult<()> {
+        check_buffer(&data, false)?;
+        let ptr: *mut u8 = data.buf_ptr().cast();
+        let len = data.len_bytes();
+
+        let data = unsafe { std::slice::from_raw_parts(ptr, len) };
+        spi_write_progress(py, addr, data, chunk_size, &callback, |addr, data| {
+            self.0.spi_write(addr, data)
+        })
+    }
+
+    /// Compute the crc32 of a region of spi without copying it into python.
+    pub fn spi_crc32(&self, addr: u32, len: u32) -> PyResult<u32> {
+        spi_crc32(addr, len, |addr, data| self.0.spi_read(addr, data))
+    }
+
+    /// Read a region of spi straight into the file at path, i.e. to back up the rom before reflashing.
+    /// Returns the number of bytes written.
+    pub fn spi_dump_to_file(&self, py: Python, addr: u32, len: u32, path: &str) -> PyResult<u32> {
+        spi_dump_to_file(py, addr, len, path, |addr, data| {
+            self.0.spi_read(addr, data)
+        })
     }
 }
 
@@ -1319,7 +3964,7 @@ impl UninitPciChip {
             .clone()
             .init(&mut |_| Python::with_gil(|py| py.check_signals()));
         match chip {
-            Ok(chip) => Ok(PciChip(chip)),
+            Ok(chip) => Ok(PciChip::from(chip)),
             Err(InitError::PlatformError(err)) => Err(PyException::new_err(err.to_string())),
             Err(InitError::CallbackError(err)) => Err(err),
         }
@@ -1332,8 +3977,17 @@ impl UninitPciChip {
             .unwrap_or(true)
     }
 
+    /// The category of the init failure (PcieDown, ArcHang, EthTimeout or DramNotTrained),
+    /// None if the chip has not failed to initialize.
+    pub fn failure_reason(&self) -> Option<String> {
+        self.chip
+            .status()
+            .and_then(|v| v.failure_reason())
+            .map(|v| v.to_string())
+    }
+
     pub fn force_upgrade(&self) -> PciChip {
-        PciChip(self.chip.clone().upgrade())
+        PciChip::from(self.chip.clone().upgrade())
     }
 
     pub fn dram_safe(&self) -> bool {
@@ -1351,22 +4005,31 @@ impl UninitPciChip {
     pub fn cpu_safe(&self) -> bool {
         self.chip.cpu_safe()
     }
-}
 
-//silent callback (import), stdout (print)
-//add arguments, (own or from luwen)
-//from luwen, multiple points to different callback functions
+    /// Block until the arc is alive or timeout_secs has elapsed, returns whether the arc is alive.
+    pub fn wait_for_arc_alive(&mut self, timeout_secs: f64) -> PyResult<bool> {
+        let timeout = std::time::Duration::try_from_secs_f64(timeout_secs)
+            .map_err(|_| PyException::new_err(format!("Invalid timeout {timeout_secs}")))?;
+        let result = self
+            .chip
+            .wait_for_arc_alive(timeout, &mut || Python::with_gil(|py| py.check_signals()));
+        match result {
+            Ok(alive) => Ok(alive),
+            Err(InitError::PlatformError(err)) => Err(PyException::new_err(err.to_string())),
+            Err(InitError::CallbackError(err)) => Err(err),
+        }
+    }
+}
 
-#[pyfunction]
-#[pyo3(signature = (interfaces = None, local_only = false, continue_on_failure = false, chip_filter = None, noc_safe = false, callback = None))]
-pub fn detect_chips_fallible(
+/// Open the requested pci interfaces (or all of them if none were given).
+/// Chips that fail a basic comms check are returned separately along with the error.
+#[allow(clippy::type_complexity)]
+fn open_root_chips(
     interfaces: Option<Vec<usize>>,
-    local_only: bool,
-    continue_on_failure: bool,
-    chip_filter: Option<Vec<String>>,
-    noc_safe: bool,
-    callback: Option<PyObject>,
-) -> PyResult<Vec<UninitPciChip>> {
+) -> PyResult<(
+    Vec<luwen_if::chip::Chip>,
+    Vec<(usize, luwen_if::chip::Chip, String)>,
+)> {
     let interfaces = interfaces.unwrap_or_default();
 
     let all_devices = luwen_ref::PciDevice::scan();
@@ -1399,20 +4062,19 @@ pub fn detect_chips_fallible(
         // important that we let the detect function know
 
         // Hack(drosen): Basic init procedure should resolve this
-        let scratch_0 = if chip.get_arch().is_blackhole() {
-            "arc_ss.reset_unit.SCRATCH_0"
-        } else {
-            "ARC_RESET.SCRATCH[0]"
-        };
-        let result = chip.axi_sread32(scratch_0);
+        let result = chip.axi_sread32(luwen_if::chip::arc_scratch_register(chip.get_arch()));
         if let Err(err) = result {
             // Basic comms have failed... we should output a nice error message on the console
-            failed_chips.push((interface, chip, err));
+            failed_chips.push((interface, chip, err.to_string()));
         } else {
             root_chips.push(chip);
         }
     }
 
+    Ok((root_chips, failed_chips))
+}
+
+fn parse_chip_filter(chip_filter: Option<Vec<String>>) -> PyResult<Vec<Arch>> {
     let chip_filter = chip_filter.unwrap_or_default();
     let mut converted_chip_filter = Vec::with_capacity(chip_filter.len());
     for filter in chip_filter {
@@ -1420,51 +4082,202 @@ pub fn detect_chips_fallible(
             PyException::new_err(format!("Could not parse chip arch: {}", value))
         })?);
     }
+
+    Ok(converted_chip_filter)
+}
+
+fn failed_chip(mut chip: luwen_if::chip::Chip, err: String) -> UninitChip {
+    let mut status = luwen_if::chip::InitStatus::new_unknown();
+    status.comms_status = luwen_if::chip::CommsStatus::CommunicationError(err);
+    status.unknown_state = false;
+    chip.init_status = Some(status.clone());
+    UninitChip::Partially {
+        status: Box::new(status),
+        underlying: chip,
+    }
+}
+
+fn component_status_dict<P: std::fmt::Display, E: std::fmt::Display>(
+    py: Python,
+    status: &luwen_if::chip::ComponentStatusInfo<P, E>,
+) -> PyResult<PyObject> {
+    use luwen_if::chip::WaitStatus;
+
+    let statuses = status
+        .wait_status
+        .iter()
+        .map(|v| match v {
+            WaitStatus::NotPresent => "not_present".to_string(),
+            WaitStatus::Waiting(_) => "waiting".to_string(),
+            WaitStatus::JustFinished | WaitStatus::Done => "done".to_string(),
+            WaitStatus::NoCheck => "no_check".to_string(),
+            WaitStatus::Timeout(_) => "timeout".to_string(),
+            WaitStatus::NotInitialized(reason) => format!("not_initialized: {reason}"),
+            WaitStatus::Error(err) => format!("error: {err}"),
+        })
+        .collect::<Vec<_>>();
+
+    let output = PyDict::new(py);
+    output.set_item("name", &status.name)?;
+    output.set_item("present", status.is_present())?;
+    output.set_item("waiting", status.is_waiting())?;
+    output.set_item("has_error", status.has_error())?;
+    output.set_item("statuses", statuses)?;
+
+    Ok(output.into())
+}
+
+/// Convert a blackhole resource layout into a dict keyed by resource name.
+fn resource_layout_dict(py: Python, chip: &luwen_if::chip::Blackhole) -> PyResult<PyObject> {
+    let layout = chip
+        .resource_layout()
+        .map_err(|v| PyException::new_err(v.to_string()))?;
+
+    let output = PyDict::new(py);
+    output.set_item("tensix_cols", layout.tensix_cols)?;
+    output.set_item("eth", layout.eth)?;
+    output.set_item("gddr", layout.gddr)?;
+    output.set_item("l2cpu", layout.l2cpu)?;
+    output.set_item("pcie", layout.pcie)?;
+
+    Ok(output.into())
+}
+
+/// Convert an init status into a dict with one entry per component,
+/// each component lists the status of every instance (i.e. each dram channel or ethernet core).
+fn init_status_dict(py: Python, status: &luwen_if::chip::InitStatus) -> PyResult<PyObject> {
+    let output = PyDict::new(py);
+    output.set_item("comms_ok", status.comms_status.ok())?;
+    output.set_item(
+        "comms_error",
+        match &status.comms_status {
+            luwen_if::chip::CommsStatus::CanCommunicate => None,
+            luwen_if::chip::CommsStatus::CommunicationError(err) => Some(err.as_str()),
+        },
+    )?;
+    output.set_item("arc", component_status_dict(py, &status.arc_status)?)?;
+    output.set_item("dram", component_status_dict(py, &status.dram_status)?)?;
+    output.set_item("eth", component_status_dict(py, &status.eth_status)?)?;
+    output.set_item("cpu", component_status_dict(py, &status.cpu_status)?)?;
+    output.set_item("noc_safe", status.init_options.noc_safe)?;
+    output.set_item("unknown_state", status.unknown_state)?;
+    output.set_item("init_complete", status.init_complete())?;
+    output.set_item(
+        "failure_reason",
+        status.failure_reason().map(|v| v.to_string()),
+    )?;
+
+    Ok(output.into())
+}
+
+//silent callback (import), stdout (print)
+//add arguments, (own or from luwen)
+//from luwen, multiple points to different callback functions
+
+/// Lower the ethernet timeout of the given chips so that a hung remote read cannot outlast `timeout`.
+/// Returns the previous timeouts so that they can be restored once detection is done.
+fn limit_eth_timeout(
+    chips: &[luwen_if::chip::Chip],
+    timeout: std::time::Duration,
+) -> Vec<(ExtendedPciDeviceWrapper, std::time::Duration)> {
+    let mut previous = Vec::new();
+    for chip in chips {
+        let device = chip
+            .as_wh()
+            .and_then(|wh| wh.get_if::<CallbackStorage<ExtendedPciDeviceWrapper>>());
+        if let Some(device) = device {
+            let mut borrow = device.user_data.borrow_mut();
+            previous.push((device.user_data.clone(), borrow.eth_timeout));
+            borrow.eth_timeout = borrow.eth_timeout.min(timeout);
+        }
+    }
+
+    previous
+}
+
+#[pyfunction]
+#[pyo3(signature = (interfaces = None, local_only = false, continue_on_failure = false, chip_filter = None, noc_safe = false, callback = None, timeout = None, tolerate_arc_failure = true, tolerate_dram_failure = true, tolerate_eth_failure = true))]
+#[allow(clippy::too_many_arguments)]
+pub fn detect_chips_fallible(
+    interfaces: Option<Vec<usize>>,
+    local_only: bool,
+    continue_on_failure: bool,
+    chip_filter: Option<Vec<String>>,
+    noc_safe: bool,
+    callback: Option<PyObject>,
+    timeout: Option<f64>,
+    tolerate_arc_failure: bool,
+    tolerate_dram_failure: bool,
+    tolerate_eth_failure: bool,
+) -> PyResult<Vec<UninitPciChip>> {
+    let timeout = timeout
+        .map(std::time::Duration::try_from_secs_f64)
+        .transpose()
+        .map_err(|v| PyException::new_err(format!("Invalid timeout: {v}")))?;
+    let deadline = timeout.map(|timeout| std::time::Instant::now() + timeout);
+
+    let (root_chips, failed_chips) = open_root_chips(interfaces)?;
+
+    let converted_chip_filter = parse_chip_filter(chip_filter)?;
     let options = ChipDetectOptions {
         continue_on_failure,
         local_only,
         chip_filter: converted_chip_filter,
         noc_safe,
+        tolerate_arc_failure,
+        tolerate_dram_failure,
+        tolerate_eth_failure,
     };
 
     #[allow(clippy::type_complexity)]
     let mut callback: Box<dyn FnMut(luwen_if::chip::ChipDetectState) -> Result<(), PyErr>> =
         if let Some(callback) = callback {
-            Box::new(move |status| {
-                // Safety: This is extremly unsafe, the alternative would be to copy the status for
-                // every invocation.
-                let status = unsafe { std::mem::transmute(status) };
-                if let Err(err) =
-                    Python::with_gil(|py| callback.call1(py, (PyChipDetectState(status),)))
-                {
-                    Err(err)
-                } else {
-                    Ok(())
-                }
-            })
+            Box::new(move |status| call_detect_callback(&callback, status))
         } else {
             Box::new(|_| Python::with_gil(|py| py.check_signals()))
         };
-    let mut chips = match luwen_if::detect_chips(root_chips, &mut callback, options) {
-        Ok(chips) => chips,
+
+    // Once the deadline passes the callback aborts detection, the chip that was being initialized at
+    // that point is kept as a partially initialized chip.
+    let mut timed_out = None;
+    let mut detect_callback = |state: luwen_if::chip::ChipDetectState| {
+        if deadline.is_some_and(|deadline| std::time::Instant::now() > deadline) {
+            timed_out = Some(UninitChip::from_detect_state(&state));
+            return Err(PyException::new_err("Chip detection timed out"));
+        }
+        callback(state)
+    };
+
+    let previous_eth_timeouts = timeout
+        .map(|timeout| limit_eth_timeout(&root_chips, timeout))
+        .unwrap_or_default();
+
+    let mut chips = Vec::new();
+    let result =
+        luwen_if::detect_chips_streaming(root_chips, &mut detect_callback, options, &mut |chip| {
+            chips.push(chip);
+            Ok(())
+        });
+
+    for (device, eth_timeout) in previous_eth_timeouts {
+        device.borrow_mut().eth_timeout = eth_timeout;
+    }
+
+    match result {
+        Ok(()) => {}
+        Err(InitError::CallbackError(_)) if timed_out.is_some() => {
+            chips.extend(timed_out.flatten());
+        }
         Err(InitError::PlatformError(err)) => {
-            return Err(PyException::new_err(err.to_string()))?;
+            return Err(PyException::new_err(err.to_string()));
         }
         Err(InitError::CallbackError(err)) => {
-            return Err(err)?;
+            return Err(err);
         }
-    };
+    }
+    // A timeout can leave fewer chips than interfaces, so a failed chip may land past the end.
     for (id, chip, err) in failed_chips.into_iter() {
-        let mut status = luwen_if::chip::InitStatus::new_unknown();
-        status.comms_status = luwen_if::chip::CommsStatus::CommunicationError(err.to_string());
-        status.unknown_state = false;
-        chips.insert(
-            id,
-            UninitChip::Partially {
-                status: Box::new(status),
-                underlying: chip,
-            },
-        );
+        chips.insert(id.min(chips.len()), failed_chip(chip, err));
     }
 
     Ok(chips
@@ -1474,7 +4287,8 @@ pub fn detect_chips_fallible(
 }
 
 #[pyfunction]
-#[pyo3(signature = (interfaces = None, local_only = false, continue_on_failure = false, chip_filter = None, noc_safe = false, callback = None))]
+#[pyo3(signature = (interfaces = None, local_only = false, continue_on_failure = false, chip_filter = None, noc_safe = false, callback = None, timeout = None, tolerate_arc_failure = true, tolerate_dram_failure = true, tolerate_eth_failure = true))]
+#[allow(clippy::too_many_arguments)]
 pub fn detect_chips(
     interfaces: Option<Vec<usize>>,
     local_only: bool,
@@ -1482,6 +4296,10 @@ pub fn detect_chips(
     chip_filter: Option<Vec<String>>,
     noc_safe: bool,
     callback: Option<PyObject>,
+    timeout: Option<f64>,
+    tolerate_arc_failure: bool,
+    tolerate_dram_failure: bool,
+    tolerate_eth_failure: bool,
 ) -> PyResult<Vec<PciChip>> {
     let chips = detect_chips_fallible(
         interfaces,
@@ -1490,35 +4308,322 @@ pub fn detect_chips(
         chip_filter,
         noc_safe,
         callback,
+        timeout,
+        tolerate_arc_failure,
+        tolerate_dram_failure,
+        tolerate_eth_failure,
     )?;
     let mut output = Vec::with_capacity(chips.len());
     for chip in chips {
+        // Finishing init would wait past the deadline, so partial chips are only available from detect_chips_fallible.
+        if timeout.is_some() && !chip.chip.is_initialized() {
+            return Err(PyException::new_err(
+                "Chip detection timed out before all chips were initialized",
+            ));
+        }
         output.push(chip.init()?);
     }
     Ok(output)
 }
 
+/// Detect every reachable chip, both pci connected and remote chips found by walking the ethernet links.
+/// Every pci connected chip is returned, a remote wormhole is dropped if a chip with the same board id and
+/// ethernet coordinate was already found. Remote chips whose board id can't be read are always returned.
+/// Use is_remote and get_local_coord on the returned chips to tell them apart.
+#[pyfunction]
+#[pyo3(signature = (interfaces = None, timeout = None))]
+pub fn detect_all_chips(
+    interfaces: Option<Vec<usize>>,
+    timeout: Option<f64>,
+) -> PyResult<Vec<PciChip>> {
+    let chips = detect_chips(
+        interfaces, false, false, None, false, None, timeout, true, true, true,
+    )?;
+
+    // Only wormhole has a real ethernet coordinate, other arches always report 0.
+    let wormhole_key = |chip: &PciChip| {
+        let wh = chip.0.as_wh()?;
+        let board_id = wh.get_telemetry().ok()?.board_id;
+        let coord = wh.get_local_chip_coord().ok()?;
+        Some((board_id, coord))
+    };
+
+    // Pci connected chips are preferred over their remote copies, so they are keyed first.
+    let mut seen: std::collections::HashSet<_> = chips
+        .iter()
+        .filter(|chip| !chip.is_remote())
+        .filter_map(wormhole_key)
+        .collect();
+
+    Ok(chips
+        .into_iter()
+        .filter(|chip| {
+            !chip.is_remote()
+                || match wormhole_key(chip) {
+                    Some(key) => seen.insert(key),
+                    None => true,
+                }
+        })
+        .collect())
+}
+
+/// Detect chips and return them in ascending board serial order.
+/// The pci enumeration order used by detect_chips can change across reboots and resets while the board serial does not,
+/// chips on the same board are ordered by their ethernet coordinate. Chips whose serial can't be read are returned last.
+#[pyfunction]
+#[pyo3(signature = (interfaces = None, local_only = false, timeout = None))]
+pub fn detect_chips_sorted_by_serial(
+    interfaces: Option<Vec<usize>>,
+    local_only: bool,
+    timeout: Option<f64>,
+) -> PyResult<Vec<PciChip>> {
+    let mut chips = detect_chips(
+        interfaces, local_only, false, None, false, None, timeout, true, true, true,
+    )?;
+
+    chips.sort_by_cached_key(|chip| {
+        let serial = chip.0.get_telemetry().ok().map(|v| v.board_serial_number());
+        let coord = chip
+            .0
+            .get_local_chip_coord()
+            .ok()
+            .map(|v| (v.rack_x, v.rack_y, v.shelf_x, v.shelf_y));
+        (serial.is_none(), serial, coord)
+    });
+
+    Ok(chips)
+}
+
+/// Detect all chips and return the ethernet map as nested dicts and lists,
+/// laid out in the same way as the yaml written by create-ethernet-map.
+#[pyfunction]
+pub fn detect_ethernet_map(py: Python) -> PyResult<PyObject> {
+    let chips: Vec<_> = detect_chips(
+        None, false, false, None, false, None, None, true, true, true,
+    )?
+    .into_iter()
+    .map(|chip| chip.0)
+    .collect();
+    let map =
+        luwen_if::EthernetMap::build(&chips).map_err(|v| PyException::new_err(v.to_string()))?;
+
+    let arch = PyDict::new(py);
+    let coords = PyDict::new(py);
+    let harvesting = PyDict::new(py);
+    let boardtype = PyDict::new(py);
+    let mut mmio_chips = Vec::new();
+    for (id, chip) in map.chips.iter().enumerate() {
+        arch.set_item(id, format!("{:?}", chip.arch))?;
+        if let Some(coord) = &chip.coord {
+            coords.set_item(
+                id,
+                [coord.shelf_x, coord.shelf_y, coord.rack_x, coord.rack_y],
+            )?;
+        }
+        if let Some(interface) = chip.mmio_interface {
+            mmio_chips.push((id, interface));
+        }
+
+        let chip_harvesting = PyDict::new(py);
+        chip_harvesting.set_item("noc_translation", chip.noc_translation_en)?;
+        chip_harvesting.set_item("harvest_mask", chip.harvest_mask)?;
+        harvesting.set_item(id, chip_harvesting)?;
+
+        boardtype.set_item(id, chip.boardtype.clone())?;
+    }
+
+    let connections = PyList::empty(py);
+    for ((local_chip, local_chan), (remote_chip, remote_chan)) in map.connections {
+        let local = PyDict::new(py);
+        local.set_item("chip", local_chip)?;
+        local.set_item("chan", local_chan)?;
+
+        let remote = PyDict::new(py);
+        remote.set_item("chip", remote_chip)?;
+        remote.set_item("chan", remote_chan)?;
+
+        connections.append(PyList::new(py, [local, remote]))?;
+    }
+
+    mmio_chips.sort_by_key(|v| v.1);
+    let chips_with_mmio = PyList::empty(py);
+    for (id, interface) in mmio_chips {
+        let entry = PyDict::new(py);
+        entry.set_item(id, interface)?;
+        chips_with_mmio.append(entry)?;
+    }
+
+    let output = PyDict::new(py);
+    output.set_item("arch", arch)?;
+    output.set_item("chips", coords)?;
+    output.set_item("ethernet_connections", connections)?;
+    output.set_item("chips_with_mmio", chips_with_mmio)?;
+    output.set_item("harvesting", harvesting)?;
+    output.set_item("boardtype", boardtype)?;
+
+    Ok(output.into())
+}
+
+/// Find the chip in `chips` (as returned by detect_chips) at ethernet address `eth_addr`
+/// and check that the noc0 coordinate (x, y) is a live core on it.
+/// Only wormhole chips are matched, returns None if none of them has that ethernet address.
+#[pyfunction]
+pub fn resolve_coordinate(
+    chips: &PyList,
+    eth_addr: EthAddr,
+    x: u8,
+    y: u8,
+) -> PyResult<Option<PyObject>> {
+    let eth_addr = luwen_if::EthAddr::from(&eth_addr);
+    for item in chips {
+        let chip: PyRef<PciChip> = item.extract()?;
+        // Only wormhole chips have an ethernet address, the coordinate reported on other arches
+        // is always 0 and would match the first chip on the mesh.
+        if chip.0.as_wh().is_none() || chip.0.get_local_chip_coord().ok() != Some(eth_addr) {
+            continue;
+        }
+
+        // Without a harvesting mask only the bounds of the grid are checked.
+        let harvesting = chip.0.get_harvesting().ok();
+        luwen_if::NocCoord::new(x, y)
+            .validate(0, chip.0.get_arch(), harvesting.as_ref())
+            .map_err(|v| {
+                noc_error(
+                    v.into(),
+                    &format!("Can't resolve ({x}, {y}) on {eth_addr}: "),
+                )
+            })?;
+
+        return Ok(Some(item.into()));
+    }
+
+    Ok(None)
+}
+
+/// Iterator over chips found by `start_detect`, each chip is returned as soon as it has been initialized.
+#[pyclass]
+pub struct DetectStream {
+    receiver: std::sync::mpsc::Receiver<Result<UninitChip, String>>,
+}
+
+#[pymethods]
+impl DetectStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python) -> PyResult<Option<UninitPciChip>> {
+        let receiver = &mut self.receiver;
+        match py.allow_threads(move || receiver.recv()) {
+            Ok(Ok(chip)) => Ok(Some(UninitPciChip { chip })),
+            Ok(Err(err)) => Err(PyException::new_err(err)),
+            // The detect thread has finished and all chips have been returned.
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+/// Start chip detection in the background and return a stream of the chips as they are found.
+/// Detection always continues on failure so that a chip which fails to initialize is returned as
+/// a partially initialized chip instead of stopping the search.
+#[pyfunction]
+#[pyo3(signature = (interfaces = None, local_only = false, chip_filter = None, noc_safe = false))]
+pub fn start_detect(
+    interfaces: Option<Vec<usize>>,
+    local_only: bool,
+    chip_filter: Option<Vec<String>>,
+    noc_safe: bool,
+) -> PyResult<DetectStream> {
+    let (root_chips, failed_chips) = open_root_chips(interfaces)?;
+
+    let options = ChipDetectOptions {
+        continue_on_failure: true,
+        local_only,
+        chip_filter: parse_chip_filter(chip_filter)?,
+        noc_safe,
+        ..Default::default()
+    };
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for (_, chip, err) in failed_chips {
+            if sender.send(Ok(failed_chip(chip, err))).is_err() {
+                return;
+            }
+        }
+
+        // A send error means that the stream was dropped, returning it will stop the search.
+        let result =
+            luwen_if::detect_chips_streaming(root_chips, &mut |_| Ok(()), options, &mut |chip| {
+                sender.send(Ok(chip)).map_err(|_| ())
+            });
+        if let Err(InitError::PlatformError(err)) = result {
+            let _ = sender.send(Err(err.to_string()));
+        }
+    });
+
+    Ok(DetectStream { receiver })
+}
+
 #[pyfunction]
 pub fn pci_scan() -> Vec<usize> {
     luwen_ref::PciDevice::scan()
 }
 
+/// Version of the loaded tenstorrent driver or None if it could not be read.
+#[pyfunction]
+pub fn driver_version() -> Option<String> {
+    ttkmd_if::DriverVersion::get_version().map(|v| v.to_string())
+}
+
 #[pymodule]
-fn pyluwen(_py: Python, m: &PyModule) -> PyResult<()> {
+fn pyluwen(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<PciChip>()?;
     m.add_class::<UninitPciChip>()?;
     m.add_class::<PciWormhole>()?;
     m.add_class::<RemoteWormhole>()?;
     m.add_class::<PciGrayskull>()?;
     m.add_class::<DmaBuffer>()?;
+    m.add_class::<TlbWindow>()?;
     m.add_class::<AxiData>()?;
+    m.add_class::<DmaCaps>()?;
     m.add_class::<Telemetry>()?;
+    m.add_class::<BoardInfo>()?;
+    m.add_class::<ChipHealth>()?;
+    m.add_class::<ComponentStatus>()?;
+    m.add_class::<DetectingChip>()?;
+    m.add_class::<DetectStream>()?;
 
     m.add_class::<PciBlackhole>()?;
 
+    m.add("NocError", py.get_type::<NocError>())?;
+    m.add("NocTimeoutError", py.get_type::<NocTimeoutError>())?;
+    m.add(
+        "NocInvalidCoordError",
+        py.get_type::<NocInvalidCoordError>(),
+    )?;
+    m.add(
+        "NocTlbUnavailableError",
+        py.get_type::<NocTlbUnavailableError>(),
+    )?;
+    m.add(
+        "NocHardwareFaultError",
+        py.get_type::<NocHardwareFaultError>(),
+    )?;
+    m.add(
+        "DevicePermissionError",
+        py.get_type::<DevicePermissionError>(),
+    )?;
+
     m.add_wrapped(wrap_pyfunction!(detect_chips))?;
     m.add_wrapped(wrap_pyfunction!(detect_chips_fallible))?;
+    m.add_wrapped(wrap_pyfunction!(detect_all_chips))?;
+    m.add_wrapped(wrap_pyfunction!(detect_chips_sorted_by_serial))?;
+    m.add_wrapped(wrap_pyfunction!(start_detect))?;
+    m.add_wrapped(wrap_pyfunction!(detect_ethernet_map))?;
+    m.add_wrapped(wrap_pyfunction!(resolve_coordinate))?;
     m.add_wrapped(wrap_pyfunction!(pci_scan))?;
+    m.add_wrapped(wrap_pyfunction!(driver_version))?;
 
     Ok(())
 }
@@ -4,6 +4,8 @@
 use nix::errno::Errno;
 use thiserror::Error;
 
+use crate::DriverVersion;
+
 #[derive(Error, Debug)]
 pub enum CfgFailType {
     #[error("Nix error: {0}")]
@@ -18,6 +20,9 @@ pub enum PciOpenError {
     #[error("Failed to open device /dev/tenstorrent/{id}: {source}")]
     DeviceOpenFailed { id: usize, source: std::io::Error },
 
+    #[error("Permission denied opening /dev/tenstorrent/{id}; the device exists but the current user cannot read and write it. Add the user to the group that owns the device, or install udev rules that grant access")]
+    PermissionDenied { id: usize },
+
     #[error("ioctl {name} failed for device {id} with: {source}")]
     IoctlError {
         name: String,
@@ -28,6 +33,19 @@ pub enum PciOpenError {
     #[error("Failed to map {name} from device {id}")]
     BarMappingError { name: String, id: usize },
 
+    #[error("Failed to map {name} from device {id}: {source}")]
+    BarMappingFailed {
+        name: String,
+        id: usize,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to open config space for device {id}: {source}")]
+    ConfigSpaceOpenFailed { id: usize, source: std::io::Error },
+
+    #[error("Device {id} has unrecognized pci device id {pci_device_id:#x}")]
+    UnrecognizedDeviceId { id: usize, pci_device_id: u16 },
+
     #[error("When creating anon buffer {buffer} for device {device_id} hit error {source}")]
     FakeMmapFailed {
         buffer: String,
@@ -56,6 +74,27 @@ pub enum PciError {
     #[error("On device {id} tried to write {size} bytes, but DMA only allows a max of 28 bits")]
     DmaTooLarge { id: usize, size: usize },
 
+    #[error("DMA transfer on device {id} did not complete within {timeout:?}")]
+    DmaTimeout {
+        id: usize,
+        timeout: std::time::Duration,
+    },
+
+    #[error("DMA transfer on device {id} failed after {offset} of {size} bytes: {source}")]
+    DmaChunkFailed {
+        id: usize,
+        offset: u32,
+        size: u32,
+        source: Box<PciError>,
+    },
+
+    #[error("DMA transfer on device {id} of {size} bytes at chip address {chip_addr:#x} runs past the end of the address space")]
+    DmaOutOfRange {
+        id: usize,
+        chip_addr: u32,
+        size: u32,
+    },
+
     #[error("Read 0xffffffff from ARC scratch[6]: you should reset the board.")]
     BrokenConnection,
 
@@ -78,4 +117,16 @@ pub enum PciError {
 
     #[error("Tried to access tlb {id} which is out of range")]
     TlbOutOfRange { id: usize },
+
+    #[error("Device {id} does not support tlb ordering {ordering} for this mapping")]
+    TlbOrderingUnsupported { id: usize, ordering: u8 },
+
+    #[error("Driver version {found} is older than the required version {required}")]
+    DriverVersionTooOld {
+        found: DriverVersion,
+        required: DriverVersion,
+    },
+
+    #[error("Could not read the driver version from {path}")]
+    DriverVersionUnavailable { path: String },
 }
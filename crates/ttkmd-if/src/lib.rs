@@ -14,24 +14,16 @@ pub mod ioctl;
 mod kmdif;
 mod pci;
 pub mod tlb;
+mod version;
 
 pub use error::{PciError, PciOpenError};
 use ioctl::{
-    query_mappings, AllocateDmaBuffer, GetDeviceInfo, GetDeviceInfoOut, Mapping, QueryMappings,
+    query_mappings, AllocateDmaBuffer, GetDeviceInfo, GetDriverInfo, Mapping, QueryMappings,
 };
 use luwen_core::Arch;
+pub use pci::dma_chip_addr;
 pub use tlb::{DeviceTlbInfo, Tlb};
-
-impl From<&GetDeviceInfoOut> for Arch {
-    fn from(value: &GetDeviceInfoOut) -> Self {
-        match value.device_id {
-            0xfaca => Arch::Grayskull,
-            0x401e => Arch::Wormhole,
-            0xb140 => Arch::Blackhole,
-            id => Arch::Unknown(id),
-        }
-    }
-}
+pub use version::DriverVersion;
 
 pub struct DmaBuffer {
     pub buffer: memmap2::MmapMut,
@@ -52,10 +44,28 @@ pub struct DmaConfig {
 
     pub support_64_bit_dma: bool,
 
+    /// Wait for completion by yielding the thread rather than spinning on the completion flag.
     pub use_msi_for_dma: bool,
 
     pub read_threshold: u32,
     pub write_threshold: u32,
+
+    /// How long to wait for a single dma transfer to complete before returning an error.
+    pub completion_timeout: std::time::Duration,
+}
+
+/// What the host and device support for dma, use this to pick the values passed in [`DmaConfig`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DmaCapabilities {
+    /// The number of physical address bits the kernel allows the device to use for dma,
+    /// 0 if this could not be read from sysfs.
+    pub max_address_bits: u32,
+
+    /// Log2 of the largest dma buffer that the driver will allocate.
+    pub max_dma_buf_size_log2: u32,
+
+    /// Whether the device has an MSI capability, required for `use_msi_for_dma`.
+    pub msi: bool,
 }
 
 pub struct PhysicalDevice {
@@ -71,6 +81,23 @@ pub struct PhysicalDevice {
 
     pub bar_addr: u64,
     pub bar_size_bytes: u64,
+
+    /// PCIe link width and generation read from sysfs when the device was opened,
+    /// these are 0 if sysfs could not be read.
+    pub pcie_current_link_width: u32,
+    pub pcie_current_link_gen: i32,
+    pub pcie_max_link_width: u32,
+    pub pcie_max_link_gen: i32,
+}
+
+impl PhysicalDevice {
+    /// The pci address of the device, formatted as domain:bus:slot.function.
+    pub fn bdf(&self) -> String {
+        format!(
+            "{:04x}:{:02x}:{:02x}.{:x}",
+            self.pci_domain, self.pci_bus, self.slot, self.pci_function
+        )
+    }
 }
 
 #[allow(dead_code)]
@@ -80,6 +107,11 @@ pub struct PciDevice {
     pub physical: PhysicalDevice,
     pub arch: Arch,
 
+    /// The api version reported by the kernel driver when the device was opened, 0 if it could not be read.
+    pub driver_version: u32,
+
+    /// When a read returns 0xffffffff, `read_checking_addr` is read to check whether the device is still
+    /// responding. This is enabled by default and probes ARC scratch6 on wh/gs or the noc node id on bh.
     pub read_checking_enabled: bool,
     pub read_checking_addr: u32,
 
@@ -160,14 +192,36 @@ fn allocate_dma_buffer(
     Ok(output)
 }
 
+fn default_read_checking_addr(arch: Arch) -> u32 {
+    if arch.is_blackhole() {
+        kmdif::BH_NOC_NODE_ID_OFFSET
+    } else {
+        kmdif::GS_WH_ARC_SCRATCH6_ADDR
+    }
+}
+
 impl PciDevice {
     pub fn open(device_id: usize) -> Result<PciDevice, PciOpenError> {
+        Self::open_inner(device_id, None)
+    }
+
+    /// Open the device as `arch` without checking the pci device id.
+    /// This is only meant for bring-up of boards that don't report their final device id yet,
+    /// using the wrong arch will access the chip at the wrong addresses.
+    pub fn open_with_arch(device_id: usize, arch: Arch) -> Result<PciDevice, PciOpenError> {
+        Self::open_inner(device_id, Some(arch))
+    }
+
+    fn open_inner(device_id: usize, forced_arch: Option<Arch>) -> Result<PciDevice, PciOpenError> {
         let fd = std::fs::OpenOptions::new()
             .read(true)
             .write(true)
             .open(format!("/dev/tenstorrent/{device_id}"));
         let fd = match fd {
             Ok(fd) => fd,
+            Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => {
+                return Err(PciOpenError::PermissionDenied { id: device_id })
+            }
             Err(err) => {
                 return Err(PciOpenError::DeviceOpenFailed {
                     id: device_id,
@@ -189,6 +243,22 @@ impl PciDevice {
 
         let max_dma_buf_size_log2 = device_info.output.max_dma_buf_size_log2;
 
+        let mut driver_info = GetDriverInfo::default();
+        driver_info.input.output_size_bytes = std::mem::size_of::<ioctl::GetDriverInfoOut>() as u32;
+
+        // The driver version is only informational, so a device can still be opened without it.
+        let driver_version = match unsafe {
+            ioctl::get_driver_info(fd.as_raw_fd(), &mut driver_info)
+        } {
+            Ok(_) => driver_info.output.driver_version,
+            Err(errorno) => {
+                tracing::warn!(
+                    "Could not read the driver version of device {device_id}, get_driver_info failed with {errorno}"
+                );
+                0
+            }
+        };
+
         let mut mappings = QueryMappings::<8>::default();
 
         if let Err(erno) = unsafe { query_mappings(fd.as_raw_fd(), &mut mappings) } {
@@ -199,7 +269,29 @@ impl PciDevice {
             });
         }
 
-        let arch = Arch::from(&device_info.output);
+        let arch = match forced_arch {
+            Some(Arch::Unknown(pci_device_id)) => {
+                return Err(PciOpenError::UnrecognizedDeviceId {
+                    id: device_id,
+                    pci_device_id,
+                })
+            }
+            Some(arch) => {
+                tracing::warn!(
+                    "Forcing device {device_id} with pci device id {:#x} to be opened as {arch}, this skips the device id check!",
+                    device_info.output.device_id
+                );
+                arch
+            }
+            None => {
+                Arch::from_device_id(device_info.output.device_id).map_err(|pci_device_id| {
+                    PciOpenError::UnrecognizedDeviceId {
+                        id: device_id,
+                        pci_device_id,
+                    }
+                })?
+            }
+        };
 
         let mut bar0_uc_mapping = Mapping::default();
         let mut bar0_wc_mapping = Mapping::default();
@@ -295,8 +387,12 @@ impl PciDevice {
         };
         let bar0_uc = match bar0_uc {
             Ok(map) => map,
-            Err(err) => {
-                panic!("Failed to map bar0_uc for {device_id} with error {err}");
+            Err(source) => {
+                return Err(PciOpenError::BarMappingFailed {
+                    name: "bar0_uc".to_string(),
+                    id: device_id,
+                    source,
+                });
             }
         };
 
@@ -312,7 +408,10 @@ impl PciDevice {
         let mut system_reg_mapping = None;
         if arch.is_wormhole() {
             if bar2_uc_mapping.mapping_id != kmdif::MappingId::Resource2Uc.as_u32() {
-                panic!("Device {device_id} has no BAR4 mapping");
+                return Err(PciOpenError::BarMappingError {
+                    name: "bar4_uc_mapping".to_string(),
+                    id: device_id,
+                });
             }
 
             system_reg_mapping_size = bar2_uc_mapping.mapping_size as usize;
@@ -324,8 +423,12 @@ impl PciDevice {
             };
             system_reg_mapping = match system_reg {
                 Ok(map) => Some(map),
-                Err(err) => {
-                    panic!("BAR4 mapping failed for device {device_id} with error {err}");
+                Err(source) => {
+                    return Err(PciOpenError::BarMappingFailed {
+                        name: "bar4_uc".to_string(),
+                        id: device_id,
+                        source,
+                    });
                 }
             };
 
@@ -337,17 +440,29 @@ impl PciDevice {
         let mut bar1_uc_size = 0;
         if arch.is_blackhole() {
             if bar1_uc_mapping.mapping_id != kmdif::MappingId::Resource1Uc.as_u32() {
-                panic!("Device {device_id} has not Bar1 UC mapping");
+                return Err(PciOpenError::BarMappingError {
+                    name: "bar1_uc_mapping".to_string(),
+                    id: device_id,
+                });
             }
 
             bar1_uc_size = bar1_uc_mapping.mapping_size;
-            bar1_uc = Some(unsafe {
+            let bar1_map = unsafe {
                 memmap2::MmapOptions::default()
                     .len(bar1_uc_mapping.mapping_size as usize)
                     .offset(bar1_uc_mapping.mapping_base)
                     .map_mut(fd.as_raw_fd())
-                    .expect("Bar1 mapping failed for device {device_id}")
-            });
+            };
+            bar1_uc = match bar1_map {
+                Ok(map) => Some(map),
+                Err(source) => {
+                    return Err(PciOpenError::BarMappingFailed {
+                        name: "bar1_uc".to_string(),
+                        id: device_id,
+                        source,
+                    });
+                }
+            };
         }
 
         let pci_bus = device_info.output.bus_dev_fn >> 8;
@@ -355,17 +470,22 @@ impl PciDevice {
         let pci_function = (device_info.output.bus_dev_fn) & 0x7; // The definition of PCI_FUNC from include/uapi/linux/pci.h
         let pci_domain = device_info.output.pci_domain;
 
+        let sysfs_path = format!(
+            "/sys/bus/pci/devices/{:04x}:{:02x}:{:02x}.{:01x}",
+            pci_domain, pci_bus, slot, pci_function
+        );
+
         let config_space = std::fs::OpenOptions::new()
             .read(true)
             .write(false)
-            .open(format!(
-                "/sys/bus/pci/devices/{:04x}:{:02x}:{:02x}.{:01x}/config",
-                pci_domain, pci_bus, slot, pci_function
-            ));
+            .open(format!("{sysfs_path}/config"));
         let config_space = match config_space {
             Ok(file) => file,
-            Err(err) => {
-                panic!("Failed to open config space for device {device_id} with error {err}");
+            Err(source) => {
+                return Err(PciOpenError::ConfigSpaceOpenFailed {
+                    id: device_id,
+                    source,
+                });
             }
         };
 
@@ -382,16 +502,18 @@ impl PciDevice {
                 slot,
                 pci_function,
                 pci_domain,
-                bar_addr: pci::read_bar0_base(&config_space),
+                bar_addr: pci::read_bar0_base(device_id, &config_space)?,
                 bar_size_bytes: bar0_uc_mapping.mapping_size,
+                pcie_current_link_width: pci::read_link_width(&sysfs_path, "current"),
+                pcie_current_link_gen: pci::read_link_gen(&sysfs_path, "current"),
+                pcie_max_link_width: pci::read_link_width(&sysfs_path, "max"),
+                pcie_max_link_gen: pci::read_link_gen(&sysfs_path, "max"),
             },
 
+            driver_version,
+
             read_checking_enabled: true,
-            read_checking_addr: if arch.is_blackhole() {
-                kmdif::BH_NOC_NODE_ID_OFFSET
-            } else {
-                kmdif::GS_WH_ARC_SCRATCH6_ADDR
-            },
+            read_checking_addr: default_read_checking_addr(arch),
 
             next_dma_buf: 0,
 
@@ -426,9 +548,24 @@ impl PciDevice {
         // buffers now.
         device.allocate_transfer_buffers();
 
+        // No driver version allocates tlbs for us yet, so every version programs the tlb
+        // registers directly. Logged to help debug tlb setup failures on unexpected drivers.
+        tracing::debug!(
+            "Opened device {device_id} ({arch}) with driver api version {driver_version}, tlbs are programmed directly through bar0"
+        );
+
         Ok(device)
     }
 
+    /// Enable or disable the check for a broken connection after reading 0xffffffff.
+    /// If `addr` is None the default register for the arch is probed.
+    /// Reading the probe register can hang if the ARC is hung or in reset, so tools should
+    /// disable the check or point it at a known-safe register before a risky sequence.
+    pub fn set_read_checking(&mut self, enabled: bool, addr: Option<u32>) {
+        self.read_checking_enabled = enabled;
+        self.read_checking_addr = addr.unwrap_or_else(|| default_read_checking_addr(self.arch));
+    }
+
     pub fn allocate_transfer_buffers(&mut self) -> bool {
         // Try to allocate the transfer buffer first, if this fails then there is no point in
         // allocating the completion flag.
@@ -3,32 +3,124 @@
 
 use std::os::fd::AsRawFd;
 
-use crate::{error::PciError, kmdif, PciDevice};
+use crate::{error::PciError, kmdif, DmaCapabilities, PciDevice};
 
 const ERROR_VALUE: u32 = 0xffffffff;
 
-pub(crate) fn read_bar0_base(config_space: &std::fs::File) -> u64 {
+pub(crate) fn read_bar0_base(id: usize, config_space: &std::fs::File) -> Result<u64, PciError> {
     const BAR_ADDRESS_MASK: u64 = !0xFu64;
 
     let bar0_config_offset = 0x10;
 
     let mut bar01 = [0u8; std::mem::size_of::<u64>()];
     let size = nix::sys::uio::pread(config_space.as_raw_fd(), &mut bar01, bar0_config_offset);
-    match size {
-        Ok(size) => {
-            if size != std::mem::size_of::<u64>() {
-                panic!("Failed to read BAR0 config space: {}", size);
-            }
+    let source = match size {
+        Ok(size) if size == bar01.len() => {
+            return Ok(u64::from_ne_bytes(bar01) & BAR_ADDRESS_MASK);
         }
+        Ok(size) => crate::error::CfgFailType::SizeMismatch(size),
+        Err(err) => crate::error::CfgFailType::Nix(err),
+    };
+
+    Err(PciError::CfgReadFailed {
+        id,
+        offset: bar0_config_offset as usize,
+        size: bar01.len(),
+        source,
+    })
+}
+
+/// Read a PCIe link attribute such as `current_link_width` from the sysfs directory of a device.
+fn read_link_attr(sysfs_path: &str, attr: &str) -> Option<String> {
+    let path = format!("{sysfs_path}/{attr}");
+    match std::fs::read_to_string(&path) {
+        Ok(value) => Some(value.trim().to_string()),
         Err(err) => {
-            panic!("Failed to read BAR0 config space: {}", err);
+            tracing::warn!("Could not read PCIe link info from {path}: {err}");
+            None
         }
     }
+}
 
-    u64::from_ne_bytes(bar01) & BAR_ADDRESS_MASK
+/// Read the link width; valid values of `kind` are "current" and "max".
+/// Returns 0 if the width could not be read.
+pub(crate) fn read_link_width(sysfs_path: &str, kind: &str) -> u32 {
+    read_link_attr(sysfs_path, &format!("{kind}_link_width"))
+        .and_then(|width| width.parse().ok())
+        .unwrap_or(0)
 }
 
+/// Read the link generation; valid values of `kind` are "current" and "max".
+/// Returns 0 if the speed could not be read and -1 if it does not match a known generation.
+pub(crate) fn read_link_gen(sysfs_path: &str, kind: &str) -> i32 {
+    let Some(speed) = read_link_attr(sysfs_path, &format!("{kind}_link_speed")) else {
+        return 0;
+    };
+
+    match speed.split_whitespace().next().unwrap_or("") {
+        "2.5" => 1,
+        "5.0" => 2,
+        "8.0" => 3,
+        "16.0" => 4,
+        "32.0" => 5,
+        "64.0" => 6,
+        _ => -1,
+    }
+}
+
+/// Capability id of MSI in the pci capability list.
+const PCI_CAP_ID_MSI: u8 = 0x05;
+
 impl PciDevice {
+    /// Query what dma configurations the host and device support.
+    /// The address width comes from the dma mask that the driver set in sysfs,
+    /// msi support is found by walking the capability list in config space.
+    pub fn dma_capabilities(&self) -> Result<DmaCapabilities, PciError> {
+        let path = format!("/sys/bus/pci/devices/{}/dma_mask_bits", self.physical.bdf());
+        let max_address_bits = match std::fs::read_to_string(&path) {
+            Ok(value) => value.trim().parse().unwrap_or(0),
+            Err(err) => {
+                tracing::warn!("Could not read dma mask from {path}: {err}");
+                0
+            }
+        };
+
+        Ok(DmaCapabilities {
+            max_address_bits,
+            max_dma_buf_size_log2: self.max_dma_buf_size_log2 as u32,
+            msi: self.has_capability(PCI_CAP_ID_MSI)?,
+        })
+    }
+
+    fn has_capability(&self, cap_id: u8) -> Result<bool, PciError> {
+        let mut status = [0u8; 2];
+        self.read_cfg(0x06, &mut status)?;
+        // Capabilities list bit in the status register
+        if u16::from_le_bytes(status) & (1 << 4) == 0 {
+            return Ok(false);
+        }
+
+        let mut ptr = [0u8; 1];
+        self.read_cfg(0x34, &mut ptr)?;
+        let mut ptr = ptr[0] & !0x3;
+
+        // Config space only fits 48 capabilities, stop there in case the list is malformed.
+        for _ in 0..48 {
+            if ptr == 0 {
+                break;
+            }
+
+            let mut cap = [0u8; 2];
+            self.read_cfg(ptr as u32, &mut cap)?;
+            if cap[0] == cap_id {
+                return Ok(true);
+            }
+            ptr = cap[1] & !0x3;
+        }
+
+        Ok(false)
+    }
+
     pub fn read_cfg(&self, byte_offset: u32, data: &mut [u8]) -> Result<(), PciError> {
         let size = nix::sys::uio::pread(self.config_space.as_raw_fd(), data, byte_offset as i64);
         match size {
@@ -99,6 +191,33 @@ impl PciDevice {
         Ok(())
     }
 
+    /// Returns true if `addr` is accessed through the write combined mapping of bar0.
+    pub fn is_wc_addr(&self, addr: u32) -> bool {
+        !(self.system_reg_mapping.is_some() && addr >= self.system_reg_start_offset)
+            && self.bar0_wc.is_some()
+            && (addr as u64) < self.bar0_wc_size
+    }
+
+    /// Drain the cpu write combining buffers, so that all earlier writes through the wc mapping
+    /// have reached the device before any later access is made.
+    #[inline]
+    pub fn flush_wc(&self) {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: sfence is available on every x86_64 cpu.
+        unsafe {
+            core::arch::x86_64::_mm_sfence()
+        };
+
+        #[cfg(target_arch = "aarch64")]
+        // SAFETY: dsb only orders memory accesses, it has no other side effects.
+        unsafe {
+            core::arch::asm!("dsb st", options(nostack, preserves_flags))
+        };
+
+        #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+        std::sync::atomic::fence(std::sync::atomic::Ordering::SeqCst);
+    }
+
     unsafe fn register_address_mut<T>(&self, mut register_addr: u32) -> *mut T {
         let reg_mapping: *mut u8;
 
@@ -226,7 +345,9 @@ impl PciDevice {
                 .with_size_bytes(size)
                 .with_write(write)
                 .with_pcie_msi_on_done(dma_config.use_msi_for_dma)
-                .with_pcie_write_on_done(!dma_config.use_msi_for_dma)
+                // The msi is handled by the driver, so the completion flag is always needed to
+                // see when the transfer has finished.
+                .with_pcie_write_on_done(true)
                 .with_trigger(true),
             repeat: 1 | (((host_phys_addr_hi != 0) as u32) << 31), // 64-bit PCIe DMA transfer request
         };
@@ -257,17 +378,61 @@ impl PciDevice {
         arc_misc_cntl_value |= 1 << 16; // Cause IRQ0 on core 0
         self.write32(dma_config.arc_misc_cntl_addr, arc_misc_cntl_value)?;
 
-        if !dma_config.use_msi_for_dma {
-            loop {
-                // The complete flag is set ty by ARC (see src/hardware/soc/tb/arc_fw/lib/pcie_dma.c)
-                unsafe {
-                    if complete_flag.read_volatile() == 0xfaca {
-                        break;
-                    }
-                }
+        let start = std::time::Instant::now();
+        // The complete flag is set ty by ARC (see src/hardware/soc/tb/arc_fw/lib/pcie_dma.c)
+        while unsafe { complete_flag.read_volatile() } != 0xfaca {
+            if start.elapsed() > dma_config.completion_timeout {
+                return Err(PciError::DmaTimeout {
+                    id: self.id,
+                    timeout: dma_config.completion_timeout,
+                });
             }
-        } else {
-            unimplemented!("Do not currently support MSI based dma");
+
+            if dma_config.use_msi_for_dma {
+                std::thread::yield_now();
+            } else {
+                std::hint::spin_loop();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Perform a dma transfer of any size by splitting it into transfers of at most MAX_DMA_BYTES.
+    /// The host buffer must be physically contiguous for the full size of the transfer.
+    pub fn pcie_dma_transfer_chunked(
+        &mut self,
+        chip_addr: u32,
+        host_buffer_addr: u64,
+        size: u32,
+        write: bool,
+    ) -> Result<(), PciError> {
+        if chip_addr.checked_add(size).is_none()
+            || host_buffer_addr.checked_add(size as u64).is_none()
+        {
+            return Err(PciError::DmaOutOfRange {
+                id: self.id,
+                chip_addr,
+                size,
+            });
+        }
+
+        let mut offset = 0;
+        while offset < size {
+            let chunk_size = (size - offset).min(kmdif::MAX_DMA_BYTES);
+            self.pcie_dma_transfer_turbo(
+                chip_addr + offset,
+                host_buffer_addr + offset as u64,
+                chunk_size,
+                write,
+            )
+            .map_err(|source| PciError::DmaChunkFailed {
+                id: self.id,
+                offset,
+                size,
+                source: Box::new(source),
+            })?;
+            offset += chunk_size;
         }
 
         Ok(())
@@ -509,3 +674,31 @@ impl PciDevice {
         }
     }
 }
+
+/// Convert the bar offset of a tlb window into the chip side address of a dma transfer of `len` bytes.
+/// The ARC dma engine only takes a 32 bit chip address, so a window in a 64 bit bar (or one that
+/// crosses the 4 GB boundary) can't be reached and None is returned.
+pub fn dma_chip_addr(bar_addr: u64, len: u64) -> Option<u32> {
+    let end = bar_addr.checked_add(len)?;
+    if end > 1 << 32 {
+        return None;
+    }
+
+    Some(bar_addr as u32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::dma_chip_addr;
+
+    #[test]
+    fn dma_chip_addr_bounds() {
+        assert_eq!(dma_chip_addr(0x1000, 0x100), Some(0x1000));
+        assert_eq!(dma_chip_addr(0xffff_f000, 0x1000), Some(0xffff_f000));
+        // Crosses the 4 GB boundary.
+        assert_eq!(dma_chip_addr(0xffff_f000, 0x1001), None);
+        // A window in a 64 bit bar.
+        assert_eq!(dma_chip_addr(0x1_0000_0000, 0x100), None);
+        assert_eq!(dma_chip_addr(u64::MAX, 1), None);
+    }
+}
@@ -198,6 +198,14 @@ pub fn setup_tlb(
     tlb_index: u32,
     mut tlb: Tlb,
 ) -> Result<(u64, u64), PciError> {
+    // Multicast writes can't use posted strict ordering.
+    if tlb.mcast && matches!(tlb.ordering, Ordering::PostedStrict) {
+        return Err(PciError::TlbOrderingUnsupported {
+            id: device.id,
+            ordering: tlb.ordering.into(),
+        });
+    }
+
     const TLB_CONFIG_BASE: u64 = 0x1FC00000;
     const TLB_CONFIG_SIZE: u64 = (32 * 3) / 8;
 
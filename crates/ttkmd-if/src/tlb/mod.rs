@@ -24,6 +24,7 @@ impl From<u8> for Ordering {
             0 => Self::RELAXED,
             1 => Self::STRICT,
             2 => Self::POSTED,
+            3 => Self::PostedStrict,
             val => Self::UNKNOWN(val),
         }
     }
@@ -86,6 +87,21 @@ pub struct DeviceTlbInfo {
     pub tlb_config: Vec<TlbInfo>,
 }
 
+impl DeviceTlbInfo {
+    /// The size of the window mapped by the tlb at `index`, or None if the index is out of range.
+    pub fn tlb_size(&self, index: u32) -> Option<u64> {
+        let mut index = index as u64;
+        for config in &self.tlb_config {
+            if index < config.count {
+                return Some(config.size);
+            }
+            index -= config.count;
+        }
+
+        None
+    }
+}
+
 pub fn get_tlb(device: &PciDevice, index: u32) -> Result<Tlb, PciError> {
     match device.arch {
         crate::Arch::Grayskull => grayskull::get_tlb(device, index),
@@ -185,6 +185,14 @@ pub fn setup_tlb(
     tlb_index: u32,
     mut tlb: Tlb,
 ) -> Result<(u64, u64), PciError> {
+    // Posted strict ordering only exists on blackhole.
+    if matches!(tlb.ordering, Ordering::PostedStrict) {
+        return Err(PciError::TlbOrderingUnsupported {
+            id: device.id,
+            ordering: tlb.ordering.into(),
+        });
+    }
+
     const TLB_CONFIG_BASE: u64 = 0x1FC00000;
 
     const TLB_COUNT_1M: u64 = 156;
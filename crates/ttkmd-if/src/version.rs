@@ -0,0 +1,198 @@
+// SPDX-FileCopyrightText: © 2023 Tenstorrent Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::OnceLock;
+
+use crate::{PciDevice, PciError};
+
+const DRIVER_VERSION_PATH: &str = "/sys/module/tenstorrent/version";
+
+/// Version of the loaded tenstorrent kernel driver.
+/// A version with a quirk suffix (i.e. 1.33.5-rc1) is considered to be older than
+/// the same version without one, quirks are ordered with their numbers compared by value (rc9 < rc10).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriverVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub quirk: Option<String>,
+}
+
+impl DriverVersion {
+    pub fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            quirk: None,
+        }
+    }
+
+    /// Parse a version of the form major.minor[.patch][-quirk].
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let (numbers, quirk) = match version.split_once('-') {
+            Some((numbers, quirk)) => (numbers, Some(quirk.to_string())),
+            None => (version, None),
+        };
+
+        let mut numbers = numbers.split('.');
+        let major = numbers.next()?.parse().ok()?;
+        let minor = numbers.next()?.parse().ok()?;
+        let patch = match numbers.next() {
+            Some(patch) => patch.parse().ok()?,
+            None => 0,
+        };
+        if numbers.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            major,
+            minor,
+            patch,
+            quirk,
+        })
+    }
+
+    /// Read the version of the loaded driver, the value is read from sysfs once and then cached.
+    pub fn get_version() -> Option<Self> {
+        static VERSION: OnceLock<Option<DriverVersion>> = OnceLock::new();
+
+        VERSION
+            .get_or_init(|| {
+                std::fs::read_to_string(DRIVER_VERSION_PATH)
+                    .ok()
+                    .and_then(|v| Self::parse(&v))
+            })
+            .clone()
+    }
+}
+
+impl std::fmt::Display for DriverVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(quirk) = &self.quirk {
+            write!(f, "-{quirk}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Ord for DriverVersion {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.quirk, &other.quirk) {
+                (None, None) => std::cmp::Ordering::Equal,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (Some(a), Some(b)) => cmp_quirk(a, b),
+            })
+    }
+}
+
+/// Compare two quirk suffixes with runs of digits compared by value, so that rc9 < rc10.
+fn cmp_quirk(a: &str, b: &str) -> std::cmp::Ordering {
+    fn chunks(value: &str) -> impl Iterator<Item = &str> {
+        let mut rest = value;
+        std::iter::from_fn(move || {
+            let is_digit = rest.chars().next()?.is_ascii_digit();
+            let end = rest
+                .find(|c: char| c.is_ascii_digit() != is_digit)
+                .unwrap_or(rest.len());
+            let (chunk, tail) = rest.split_at(end);
+            rest = tail;
+            Some(chunk)
+        })
+    }
+
+    let mut a_chunks = chunks(a);
+    let mut b_chunks = chunks(b);
+    loop {
+        let ordering = match (a_chunks.next(), b_chunks.next()) {
+            // Only differences in leading zeros are left, fall back to the raw strings to stay consistent with Eq.
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return std::cmp::Ordering::Less,
+            (Some(_), None) => return std::cmp::Ordering::Greater,
+            (Some(a), Some(b))
+                if a.starts_with(|c: char| c.is_ascii_digit())
+                    && b.starts_with(|c: char| c.is_ascii_digit()) =>
+            {
+                let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+                a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+            }
+            (Some(a), Some(b)) => a.cmp(b),
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+}
+
+impl PartialOrd for DriverVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PciDevice {
+    /// Return an error if the loaded driver is older than `min` or if its version could not be read.
+    pub fn require_driver_version(min: DriverVersion) -> Result<(), PciError> {
+        match DriverVersion::get_version() {
+            Some(found) if found >= min => Ok(()),
+            Some(found) => Err(PciError::DriverVersionTooOld {
+                found,
+                required: min,
+            }),
+            None => Err(PciError::DriverVersionUnavailable {
+                path: DRIVER_VERSION_PATH.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DriverVersion;
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(
+            DriverVersion::parse("1.26\n"),
+            Some(DriverVersion::new(1, 26, 0))
+        );
+        assert_eq!(
+            DriverVersion::parse("1.33.5-rc1"),
+            Some(DriverVersion {
+                quirk: Some("rc1".to_string()),
+                ..DriverVersion::new(1, 33, 5)
+            })
+        );
+        assert_eq!(DriverVersion::parse("1.x"), None);
+        assert_eq!(DriverVersion::parse("1.2.3.4"), None);
+    }
+
+    #[test]
+    fn compare_version() {
+        let rc = DriverVersion::parse("1.33.5-rc1").unwrap();
+
+        assert!(rc < DriverVersion::new(1, 33, 5));
+        assert!(rc > DriverVersion::new(1, 33, 4));
+        assert!(DriverVersion::new(1, 34, 0) > DriverVersion::new(1, 33, 12));
+
+        let quirk = |quirk: &str| DriverVersion {
+            quirk: Some(quirk.to_string()),
+            ..DriverVersion::new(1, 33, 5)
+        };
+        assert!(quirk("rc9") < quirk("rc10"));
+        assert!(quirk("rc10") < quirk("rc11"));
+        assert!(quirk("rc2") > quirk("rc1"));
+        assert!(quirk("beta3") < quirk("rc1"));
+        assert!(quirk("rc1") < quirk("rc1.1"));
+        assert!(quirk("rc01") != quirk("rc1"));
+        assert!(quirk("rc01") < quirk("rc1"));
+        assert!(quirk("rc18446744073709551616") > quirk("rc9"));
+    }
+}
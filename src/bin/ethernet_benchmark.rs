@@ -37,6 +37,7 @@ fn read_write_test(
             use_msi_for_dma: false,
             read_threshold: 32,
             write_threshold: 4096,
+            completion_timeout: std::time::Duration::from_secs(1),
         });
     }
 
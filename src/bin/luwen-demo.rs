@@ -42,6 +42,7 @@ pub fn main() -> Result<(), LuwenError> {
                     use_msi_for_dma: false,
                     read_threshold: 0,
                     write_threshold: 0,
+                    completion_timeout: std::time::Duration::from_secs(1),
                 });
 
                 let (offset, _size) = pci_interface.setup_tlb(
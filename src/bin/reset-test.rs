@@ -4,6 +4,45 @@ use luwen_if::{
 };
 use luwen_ref::detect_chips;
 
+/// Where a board was found before and after the reset, `after` is None if the board did not come back.
+#[derive(Debug)]
+struct ResetMapping {
+    serial: u64,
+    before: usize,
+    after: Option<usize>,
+}
+
+fn read_serials(interfaces: &[usize]) -> Vec<(usize, u64)> {
+    interfaces
+        .iter()
+        .map(|&interface| {
+            let chip = luwen_ref::open(interface).unwrap();
+            (
+                interface,
+                chip.get_telemetry().unwrap().board_serial_number(),
+            )
+        })
+        .collect()
+}
+
+/// Match the boards that reappeared after a reset by serial rather than by interface index,
+/// after a hotplug the same board can come back at a different interface.
+fn verify_serials(before: &[(usize, u64)]) -> Vec<ResetMapping> {
+    let after = read_serials(&luwen_ref::PciDevice::scan());
+
+    before
+        .iter()
+        .map(|&(interface, serial)| ResetMapping {
+            serial,
+            before: interface,
+            after: after
+                .iter()
+                .find(|(_, after_serial)| *after_serial == serial)
+                .map(|(after_interface, _)| *after_interface),
+        })
+        .collect()
+}
+
 fn lds_reset(interfaces: &[usize]) -> Vec<Chip> {
     for interface in interfaces {
         let fd = std::fs::OpenOptions::new()
@@ -98,8 +137,25 @@ fn main() {
     }
 
     let interfaces = luwen_ref::PciDevice::scan();
+    let serials = read_serials(&interfaces);
     lds_reset(&interfaces);
 
+    let mapping = verify_serials(&serials);
+    for board in &mapping {
+        match board.after {
+            Some(after) if after == board.before => {}
+            Some(after) => println!(
+                "Board {:016x} moved from interface {} to {after}",
+                board.serial, board.before
+            ),
+            None => println!(
+                "Board {:016x} at interface {} did not come back after reset",
+                board.serial, board.before
+            ),
+        }
+    }
+    assert!(mapping.iter().all(|board| board.after.is_some()));
+
     for interface in interfaces.iter().copied() {
         let result = luwen_ref::open(interface)
             .unwrap()